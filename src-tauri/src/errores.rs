@@ -0,0 +1,82 @@
+// ----- Errores devueltos por los comandos -----
+// Se serializan como { codigo, mensaje }: la interfaz decide por `codigo` y muestra
+// `mensaje`, sin tener que comparar textos.
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt;
+use std::io;
+use std::sync::PoisonError;
+
+#[derive(Debug)]
+pub enum ErrorSim {
+    // Un hilo entró en pánico mientras tenía tomado un mutex
+    MutexEnvenenado,
+    ProcesoNoEncontrado(u32),
+    // El proceso está en la lista de protegidos (kernel, init, la propia app...)
+    Protegido(u32),
+    // El sistema operativo rechazó la operación por falta de privilegios
+    Permiso,
+    // Cualquier otro fallo del sistema operativo o de E/S
+    Plataforma(String),
+    // Parámetros fuera de rango recibidos desde la interfaz
+    EntradaInvalida(String),
+}
+
+impl ErrorSim {
+    pub fn codigo(&self) -> &'static str {
+        match self {
+            ErrorSim::MutexEnvenenado => "mutex_envenenado",
+            ErrorSim::ProcesoNoEncontrado(_) => "proceso_no_encontrado",
+            ErrorSim::Protegido(_) => "proceso_protegido",
+            ErrorSim::Permiso => "permiso",
+            ErrorSim::Plataforma(_) => "plataforma",
+            ErrorSim::EntradaInvalida(_) => "entrada_invalida",
+        }
+    }
+
+    // Distingue la falta de permisos del resto de errores del sistema operativo
+    pub fn io(e: io::Error, contexto: &str) -> Self {
+        if e.kind() == io::ErrorKind::PermissionDenied {
+            ErrorSim::Permiso
+        } else {
+            ErrorSim::Plataforma(format!("{contexto}: {e}"))
+        }
+    }
+}
+
+impl fmt::Display for ErrorSim {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorSim::MutexEnvenenado => {
+                write!(f, "Estado interno inconsistente tras un fallo previo")
+            }
+            ErrorSim::ProcesoNoEncontrado(pid) => write!(f, "No existe un proceso con PID {pid}"),
+            ErrorSim::Protegido(pid) => write!(f, "El proceso {pid} está protegido"),
+            ErrorSim::Permiso => write!(f, "Permisos insuficientes para esta operación"),
+            ErrorSim::Plataforma(mensaje) | ErrorSim::EntradaInvalida(mensaje) => {
+                write!(f, "{mensaje}")
+            }
+        }
+    }
+}
+
+impl Serialize for ErrorSim {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("ErrorSim", 2)?;
+        s.serialize_field("codigo", self.codigo())?;
+        s.serialize_field("mensaje", &self.to_string())?;
+        s.end()
+    }
+}
+
+impl<T> From<PoisonError<T>> for ErrorSim {
+    fn from(_: PoisonError<T>) -> Self {
+        ErrorSim::MutexEnvenenado
+    }
+}
+
+// Los módulos auxiliares (exportar, persistencia) informan sus fallos como texto
+impl From<String> for ErrorSim {
+    fn from(mensaje: String) -> Self {
+        ErrorSim::Plataforma(mensaje)
+    }
+}
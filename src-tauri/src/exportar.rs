@@ -0,0 +1,148 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+
+use crate::motor::Proceso;
+
+// ----- Exportación a CSV (RFC 4180) -----
+
+// Un campo se entrecomilla si contiene separadores, comillas o saltos de línea; las
+// comillas internas se duplican.
+fn escapar_csv(campo: &str) -> String {
+    if campo.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", campo.replace('"', "\"\""))
+    } else {
+        campo.to_string()
+    }
+}
+
+fn valor_csv(valor: &Value) -> String {
+    match valor {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        // Listas y objetos anidados se vuelcan como JSON dentro de la celda
+        otro => otro.to_string(),
+    }
+}
+
+// Escribe `filas` con una cabecera derivada de sus campos serializados (en orden
+// alfabético, el de serde_json::Map) y devuelve la cantidad de filas escritas.
+pub fn escribir_csv<T: Serialize>(filas: &[T], ruta: &str) -> Result<usize, String> {
+    let filas: Vec<serde_json::Map<String, Value>> = filas
+        .iter()
+        .map(|f| match serde_json::to_value(f) {
+            Ok(Value::Object(mapa)) => Ok(mapa),
+            Ok(_) => Err("Solo se pueden exportar estructuras".to_string()),
+            Err(e) => Err(e.to_string()),
+        })
+        .collect::<Result<_, _>>()?;
+
+    let columnas: Vec<&String> = filas
+        .first()
+        .map(|f| f.keys().collect())
+        .unwrap_or_default();
+
+    let mut csv = String::new();
+    let cabecera: Vec<String> = columnas.iter().map(|c| escapar_csv(c)).collect();
+    csv.push_str(&cabecera.join(","));
+    csv.push_str("\r\n");
+    for fila in &filas {
+        let celdas: Vec<String> = columnas
+            .iter()
+            .map(|c| escapar_csv(&fila.get(*c).map(valor_csv).unwrap_or_default()))
+            .collect();
+        csv.push_str(&celdas.join(","));
+        csv.push_str("\r\n");
+    }
+
+    fs::write(ruta, csv).map_err(|e| format!("No se pudo escribir {ruta}: {e}"))?;
+    Ok(filas.len())
+}
+
+// ----- Exportación/importación JSON -----
+pub fn guardar_json<T: Serialize>(valor: &T, ruta: &str) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(valor).map_err(|e| e.to_string())?;
+    fs::write(ruta, json).map_err(|e| format!("No se pudo escribir {ruta}: {e}"))
+}
+
+pub fn leer_json<T: DeserializeOwned>(ruta: &str) -> Result<T, String> {
+    let json = fs::read_to_string(ruta).map_err(|e| format!("No se pudo leer {ruta}: {e}"))?;
+    serde_json::from_str(&json).map_err(|e| format!("Contenido inválido en {ruta}: {e}"))
+}
+
+// ----- Formato de exposición de Prometheus -----
+
+// En los valores de etiqueta solo se escapan la barra invertida, las comillas y los
+// saltos de línea; el resto del texto va tal cual.
+fn escapar_etiqueta(valor: &str) -> String {
+    valor
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+// Nombre, ayuda y cómo extraer el valor de cada métrica por proceso
+type MetricaProceso = (&'static str, &'static str, fn(&Proceso) -> f64);
+
+fn cabecera_metrica(salida: &mut String, nombre: &str, ayuda: &str) {
+    salida.push_str(&format!("# HELP {nombre} {ayuda}\n# TYPE {nombre} gauge\n"));
+}
+
+// Métricas globales `(nombre, ayuda, valor)` seguidas de las de cada proceso. Cada
+// familia va completa antes de la siguiente, como exige el formato.
+pub fn metricas_prometheus(globales: &[(&str, &str, f64)], procesos: &[Proceso]) -> String {
+    let mut salida = String::new();
+    for &(nombre, ayuda, valor) in globales {
+        cabecera_metrica(&mut salida, nombre, ayuda);
+        salida.push_str(&format!("{nombre} {valor}\n"));
+    }
+
+    let por_proceso: [MetricaProceso; 3] = [
+        ("proceso_cpu", "Uso de CPU del proceso en porcentaje", |p| {
+            p.tiempo_cpu
+        }),
+        (
+            "proceso_memoria_bytes",
+            "Memoria residente del proceso",
+            |p| p.memoria_bytes as f64,
+        ),
+        ("proceso_hilos", "Cantidad de hilos del proceso", |p| {
+            f64::from(p.hilos)
+        }),
+    ];
+    for (nombre, ayuda, valor) in por_proceso {
+        cabecera_metrica(&mut salida, nombre, ayuda);
+        for p in procesos {
+            salida.push_str(&format!(
+                "{nombre}{{pid=\"{}\",nombre=\"{}\"}} {}\n",
+                p.pid,
+                escapar_etiqueta(&p.nombre),
+                valor(p)
+            ));
+        }
+    }
+    salida
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapar_csv_sigue_rfc_4180() {
+        assert_eq!(escapar_csv("bash"), "bash");
+        assert_eq!(escapar_csv("a,b"), "\"a,b\"");
+        assert_eq!(escapar_csv("di \"hola\""), "\"di \"\"hola\"\"\"");
+        assert_eq!(escapar_csv("una\nlínea"), "\"una\nlínea\"");
+        assert_eq!(escapar_csv("fin\r"), "\"fin\r\"");
+    }
+
+    #[test]
+    fn escapar_etiqueta_sigue_el_formato_de_prometheus() {
+        assert_eq!(escapar_etiqueta("nginx"), "nginx");
+        assert_eq!(escapar_etiqueta(r"C:\bin"), r"C:\\bin");
+        assert_eq!(escapar_etiqueta("di \"hola\""), r#"di \"hola\""#);
+        assert_eq!(escapar_etiqueta("a\nb"), r"a\nb");
+    }
+}
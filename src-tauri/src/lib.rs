@@ -1,3 +1,11 @@
+pub mod errores;
+pub mod exportar;
+pub mod motor;
+pub mod persistencia;
+pub mod planificador;
+pub mod plataforma;
+pub mod simulador;
+
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
 fn greet(name: &str) -> String {
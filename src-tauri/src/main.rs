@@ -1,197 +1,624 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use once_cell::sync::Lazy;
+use rayon::prelude::*;
 use serde::Serialize;
-use std::collections::HashMap;
-use std::sync::Mutex;
-use std::time::Instant;
-use sysinfo::{PidExt, ProcessExt, ProcessStatus, System, SystemExt};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use sysinfo::{
+    ComponentExt, CpuExt, NetworkExt, NetworksExt, Pid, PidExt, ProcessExt, System, SystemExt,
+};
+use tauri::{AppHandle, Emitter, RunEvent};
 
-// ----- Estructura que serializamos al frontend -----
-#[derive(Serialize)]
-pub struct Proceso {
-    pub pid: String,
-    pub nombre: String,
-    pub prioridad: i32,
-    pub tiempo_cpu: f64,
-    pub memoria: u64,
-    pub estado: String,
-    pub interactividad: i32,
-    pub avance: f64,
-    pub iteraciones: u32,
-    pub tiempo_total: f64,
-    pub tiempo_restante: f64,
+use simulador_fifo_lib::errores::ErrorSim;
+use simulador_fifo_lib::motor::{
+    agrupar_por_nombre, construir_arbol, consultar_procesos_version, deltas_ultimas_muestras,
+    diferenciar, estado_procesos, estimar_entradas, guardar_captura, mayores_por_cpu,
+    muestra_ociosa, muestrear_proceso, procesos_capturados, reiniciar_estado_procesos, snapshot,
+    tomar_instantanea, AlCompletar, CriterioOrden, DeltaProceso, DiffInstantanea,
+    EstrategiaTiempoTotal, Filtro, GrupoProceso, Idioma, Instantanea, IntervaloAdaptativo, Muestra,
+    NodoProceso, PaginaProcesos, Proceso, ProcesoSimulado, Proteccion, RangoPid, CONFIG, FIJADOS,
+    HISTORIAL_CPU, SIGUIENTE_PID_SIMULADO, SIMULADOS, SYSTEM, VIGILADOS,
+};
+#[cfg(debug_assertions)]
+use simulador_fifo_lib::motor::{estado_interno, EstadoInterno};
+use simulador_fifo_lib::planificador::{self, Algoritmo, ProcesoEntrada, ResultadoSimulacion};
+use simulador_fifo_lib::simulador::{EstadoSimulacion, Simulador};
+use simulador_fifo_lib::{exportar, persistencia, plataforma};
+
+// ----- Configuración del muestreo -----
+
+#[tauri::command]
+fn configurar_muestreo(
+    cpu_threshold: f64,
+    ewma_alpha: f64,
+    intervalo_esperado_ms: Option<u64>,
+    cpu_threshold_salida: Option<f64>,
+    elapsed_minimo_ms: Option<u64>,
+) -> Result<(), ErrorSim> {
+    if !(ewma_alpha > 0.0 && ewma_alpha <= 1.0) {
+        return Err(ErrorSim::EntradaInvalida(format!(
+            "ewma_alpha debe estar en (0, 1], se recibió {ewma_alpha}"
+        )));
+    }
+    if !cpu_threshold.is_finite() || cpu_threshold < 0.0 {
+        return Err(ErrorSim::EntradaInvalida(format!(
+            "cpu_threshold debe ser un número no negativo, se recibió {cpu_threshold}"
+        )));
+    }
+    if let Some(salida) = cpu_threshold_salida {
+        if !salida.is_finite() || salida < 0.0 || salida > cpu_threshold {
+            return Err(ErrorSim::EntradaInvalida(format!(
+                "cpu_threshold_salida debe estar entre 0 y cpu_threshold ({cpu_threshold}), se recibió {salida}"
+            )));
+        }
+    }
+    let mut cfg = CONFIG.lock()?;
+    // Sin umbral de salida explícito se conserva el actual, sin superar el de entrada
+    cfg.cpu_threshold_salida =
+        cpu_threshold_salida.unwrap_or(cfg.cpu_threshold_salida.min(cpu_threshold));
+    cfg.cpu_threshold = cpu_threshold;
+    cfg.ewma_alpha = ewma_alpha;
+    if let Some(ms) = intervalo_esperado_ms.filter(|&ms| ms > 0) {
+        cfg.intervalo_esperado = ms as f64 / 1000.0;
+    }
+    if let Some(ms) = elapsed_minimo_ms {
+        cfg.elapsed_minimo = ms as f64 / 1000.0;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn configurar_pico_cpu(umbral: f64) -> Result<(), ErrorSim> {
+    if !(umbral.is_finite() && umbral > 0.0) {
+        return Err(ErrorSim::EntradaInvalida(format!(
+            "El umbral de pico debe ser positivo, se recibió {umbral}"
+        )));
+    }
+    CONFIG.lock()?.umbral_pico = umbral;
+    Ok(())
+}
+
+// Con un tope, obtener_procesos conserva solo N procesos: primero los fijados y luego
+// los de mayor CPU
+#[tauri::command]
+fn configurar_max_procesos(max_procesos: Option<usize>) -> Result<(), ErrorSim> {
+    if max_procesos == Some(0) {
+        return Err(ErrorSim::EntradaInvalida(
+            "max_procesos debe ser al menos 1".to_string(),
+        ));
+    }
+    CONFIG.lock()?.max_procesos = max_procesos;
+    Ok(())
+}
+
+#[tauri::command]
+fn configurar_idioma(idioma: Idioma) -> Result<(), ErrorSim> {
+    CONFIG.lock()?.idioma = idioma;
+    Ok(())
 }
 
-// ----- Estado en memoria para el muestreo entre invocaciones -----
-struct ProcStat {
-    last_seen: Instant,
-    acc_cpu_seconds: f64,
-    iteraciones: u32,
-    last_cpu_positive: bool,
-    ewma_cpu: f64,
+#[tauri::command]
+fn configurar_tiempo_total(estrategia: EstrategiaTiempoTotal) -> Result<(), ErrorSim> {
+    if let EstrategiaTiempoTotal::Fija(segundos) = estrategia {
+        if !(segundos.is_finite() && segundos > 0.0) {
+            return Err(ErrorSim::EntradaInvalida(format!(
+                "El tiempo total fijo debe ser positivo, se recibió {segundos}"
+            )));
+        }
+    }
+    CONFIG.lock()?.estrategia_tiempo_total = estrategia;
+    Ok(())
+}
+
+#[tauri::command]
+fn configurar_al_completar(modo: AlCompletar) -> Result<(), ErrorSim> {
+    CONFIG.lock()?.al_completar = modo;
+    Ok(())
+}
+
+// Reemplaza la lista de procesos protegidos (el propio siempre lo está)
+#[tauri::command]
+fn configurar_protegidos(rangos: Vec<RangoPid>, nombres: Vec<String>) -> Result<(), ErrorSim> {
+    if let Some(r) = rangos.iter().find(|r| r.desde > r.hasta) {
+        return Err(ErrorSim::EntradaInvalida(format!(
+            "Rango de PIDs inválido: {} > {}",
+            r.desde, r.hasta
+        )));
+    }
+    CONFIG.lock()?.proteccion = Proteccion { rangos, nombres };
+    Ok(())
+}
+
+// Descarta los acumuladores (avance, iteraciones...) de todos los procesos; la próxima
+// muestra los vuelve a sembrar como nuevos. Solo toma PROC_STATE, y ninguna otra ruta
+// lo mantiene bloqueado mientras espera otro mutex, así que no puede haber interbloqueo:
+// a lo sumo espera a que termine una muestra en curso.
+#[tauri::command]
+fn reiniciar_estado() {
+    reiniciar_estado_procesos();
 }
 
-static PROC_STATE: Lazy<Mutex<HashMap<u32, ProcStat>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+// Persistencia manual de los acumuladores (además de la automática al cerrar/abrir)
+#[tauri::command]
+fn guardar_estado(app: AppHandle) -> Result<usize, ErrorSim> {
+    persistencia::guardar(&app)
+}
 
-const CPU_THRESHOLD: f64 = 1.0;
-const EWMA_ALPHA: f64 = 0.25;
+#[tauri::command]
+fn cargar_estado(app: AppHandle) -> Result<usize, ErrorSim> {
+    persistencia::cargar(&app)
+}
 
+// ----- Depuración de las heurísticas (solo en builds de desarrollo) -----
+#[cfg(debug_assertions)]
 #[tauri::command]
-fn obtener_procesos() -> Result<Vec<Proceso>, String> {
+fn volcar_estado_interno() -> Vec<EstadoInterno> {
+    estado_interno()
+}
+
+// Los comandos informan como eventos de Tauri lo que pasó desde la muestra anterior
+fn emitir_eventos(instantanea: &Instantanea, app: &AppHandle) {
+    for evento in &instantanea.iniciados {
+        let _ = app.emit("proceso_iniciado", evento);
+    }
+    for evento in &instantanea.terminados {
+        let _ = app.emit("proceso_terminado", evento);
+    }
+    for pico in &instantanea.picos {
+        let _ = app.emit("pico_cpu", pico);
+    }
+    for evento in &instantanea.vigilados_terminados {
+        let _ = app.emit("proceso_vigilado_terminado", evento);
+    }
+}
+
+// ----- Procesos vigilados -----
+#[tauri::command]
+fn vigilar_proceso(pid: u32) -> Result<(), ErrorSim> {
+    let mut sys = SYSTEM.lock()?;
+    let pid_sys = Pid::from_u32(pid);
+    if !sys.refresh_process(pid_sys) {
+        return Err(ErrorSim::ProcesoNoEncontrado(pid));
+    }
+    let nombre = sys
+        .process(pid_sys)
+        .map(|p| p.name().to_string())
+        .unwrap_or_default();
+    VIGILADOS.lock()?.insert(pid, nombre);
+    Ok(())
+}
+
+// Devuelve si el PID estaba vigilado
+#[tauri::command]
+fn dejar_de_vigilar(pid: u32) -> Result<bool, ErrorSim> {
+    Ok(VIGILADOS.lock()?.remove(&pid).is_some())
+}
+
+// ----- Procesos fijados al principio de la lista -----
+#[tauri::command]
+fn fijar_proceso(pid: u32) -> Result<(), ErrorSim> {
+    verificar_proceso(pid)?;
+    let mut fijados = FIJADOS.lock()?;
+    if !fijados.contains(&pid) {
+        fijados.push(pid);
+    }
+    Ok(())
+}
+
+// Devuelve si el PID estaba fijado
+#[tauri::command]
+fn desfijar_proceso(pid: u32) -> Result<bool, ErrorSim> {
+    let mut fijados = FIJADOS.lock()?;
+    let antes = fijados.len();
+    fijados.retain(|&f| f != pid);
+    Ok(fijados.len() != antes)
+}
+
+// ----- Lista de procesos -----
+// Con `version` la respuesta es incremental (ver consultar_procesos_version).
+// Los argumentos de un comando son las claves del JSON que envía la interfaz
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+fn obtener_procesos(
+    normalizar_cpu: Option<bool>,
+    filtro: Option<Filtro>,
+    orden: Option<CriterioOrden>,
+    descendente: Option<bool>,
+    offset: Option<usize>,
+    limite: Option<usize>,
+    version: Option<u64>,
+    app: AppHandle,
+) -> Result<PaginaProcesos, ErrorSim> {
+    let instantanea = tomar_instantanea(normalizar_cpu)?;
+    emitir_eventos(&instantanea, &app);
+    // Sin filtro se aplica el predeterminado, que oculta zombis e hilos del kernel.
+    // Sin criterio explícito se conserva el orden histórico: CPU descendente
+    consultar_procesos_version(
+        instantanea.procesos,
+        &filtro.unwrap_or_default(),
+        orden.unwrap_or_default(),
+        descendente.unwrap_or(true),
+        offset,
+        limite,
+        version,
+    )
+}
+
+// ----- Perfil del muestreo -----
+// Microsegundos de cada etapa de un ciclo, para ver cuál domina en un equipo dado
+#[derive(Serialize)]
+pub struct PerfilMuestreo {
+    // Esperando PROC_STATE (otra muestra en curso lo retiene)
+    pub espera_mutex_us: u64,
+    pub refresh_processes_us: u64,
+    pub refresh_cpu_us: u64,
+    pub refresh_memory_us: u64,
+    // Construcción de cada Proceso (en paralelo con rayon)
+    pub mapeo_us: u64,
+    // Un System::new_all completo, lo que costaría no reutilizar la instancia global
+    pub new_all_us: u64,
+    pub procesos: usize,
+    // Ciclo completo, sin contar new_all_us
+    pub total_us: u64,
+}
+
+// Se mide sobre un System propio: refrescar el global reiniciaría la base del CPU % de
+// sysinfo y la muestra siguiente multiplicaría una CPU de un intervalo corto por el
+// elapsed completo. El mapeo tampoco guarda los ProcStat, así que los acumuladores no
+// cambian.
+#[tauri::command]
+fn perfil_muestreo(normalizar_cpu: Option<bool>) -> Result<PerfilMuestreo, ErrorSim> {
+    let micros = |desde: Instant| desde.elapsed().as_micros() as u64;
+
+    let t = Instant::now();
     let mut sys = System::new_all();
+    let new_all_us = micros(t);
+
+    let inicio = Instant::now();
+    let t = Instant::now();
     sys.refresh_processes();
+    let refresh_processes_us = micros(t);
+    let t = Instant::now();
     sys.refresh_cpu();
+    let refresh_cpu_us = micros(t);
+    let t = Instant::now();
     sys.refresh_memory();
+    let refresh_memory_us = micros(t);
 
-    let now = Instant::now();
-
-    let mut out: Vec<Proceso> = sys
+    let muestra = Muestra::nueva(&sys, normalizar_cpu)?;
+    let t = Instant::now();
+    let map = estado_procesos();
+    let espera_mutex_us = micros(t);
+    let t = Instant::now();
+    let procesos = sys
         .processes()
-        .iter()
+        .par_iter()
         .map(|(pid, process)| {
-            let estado = match process.status() {
-                ProcessStatus::Run => "ejecutando",
-                ProcessStatus::Sleep => "dormido",
-                ProcessStatus::Idle => "inactivo",
-                ProcessStatus::Stop => "detenido",
-                ProcessStatus::Zombie => "zombie",
-                ProcessStatus::Tracing => "trazando",
-                ProcessStatus::Unknown(_) => "desconocido",
-                _ => "desconocido",
-            }
-            .to_string();
-
-            let prioridad = 1;
-            let cpu = process.cpu_usage() as f64;
-            let mem_kb = process.memory();
-
-            // Heurística para tiempo_total: mapear CPU 0-100 -> 1-20 segundos
-            let tiempo_total = if cpu > 0.0 {
-                let v = ((cpu / 100.0) * 19.0) + 1.0;
-                v.round().clamp(1.0, 20.0)
-            } else {
-                let mem_mb = (mem_kb as f64) / 1024.0;
-                ((mem_mb / 50.0) + 10.0).round().max(5.0)
-            };
+            let pid = pid.as_u32();
+            muestrear_proceso(pid, process, &muestra, map.get(&pid)).0
+        })
+        .count();
+    let mapeo_us = micros(t);
+    drop(map);
+    let total_us = micros(inicio);
 
-            let mut tiempo_restante = tiempo_total;
-            let pid_u32 = pid.as_u32();
+    Ok(PerfilMuestreo {
+        espera_mutex_us,
+        refresh_processes_us,
+        refresh_cpu_us,
+        refresh_memory_us,
+        mapeo_us,
+        new_all_us,
+        procesos,
+        total_us,
+    })
+}
 
-            {
-                let mut map = PROC_STATE.lock().expect("failed to lock PROC_STATE mutex");
+// Solo los `n` procesos de mayor CPU, para widgets compactos. Los ProcStat de todos
+// los procesos se actualizan igual que en obtener_procesos.
+#[tauri::command]
+fn top_cpu(
+    n: usize,
+    normalizar_cpu: Option<bool>,
+    app: AppHandle,
+) -> Result<Vec<Proceso>, ErrorSim> {
+    let instantanea = tomar_instantanea(normalizar_cpu)?;
+    emitir_eventos(&instantanea, &app);
+    Ok(mayores_por_cpu(instantanea.procesos, n))
+}
 
-                if let Some(stat) = map.get_mut(&pid_u32) {
-                    let elapsed = now.duration_since(stat.last_seen).as_secs_f64();
-                    let added_cpu_seconds = (cpu / 100.0) * elapsed;
-                    stat.acc_cpu_seconds += added_cpu_seconds;
+// ----- Muestreo en segundo plano -----
+// El backend fija la cadencia: un hilo refresca la lista cada `intervalo_ms` y la
+// emite como evento, de modo que el tiempo entre muestras (el `elapsed` de los
+// acumuladores) es estable en lugar de depender del temporizador de JS.
+struct Muestreador {
+    detener: Arc<AtomicBool>,
+    hilo: JoinHandle<()>,
+}
 
-                    // Actualizar EWMA del uso de CPU
-                    stat.ewma_cpu = EWMA_ALPHA * cpu + (1.0 - EWMA_ALPHA) * stat.ewma_cpu;
+static MUESTREADOR: Lazy<Mutex<Option<Muestreador>>> = Lazy::new(|| Mutex::new(None));
 
-                    // Detectar transición a consumir CPU
-                    let now_positive = cpu > CPU_THRESHOLD;
-                    if now_positive && !stat.last_cpu_positive {
-                        stat.iteraciones = stat.iteraciones.saturating_add(1);
-                    }
-                    stat.last_cpu_positive = now_positive;
-                    stat.last_seen = now;
-
-                    if tiempo_total > 0.0 {
-                        let raw_pct = (stat.acc_cpu_seconds / tiempo_total) * 100.0;
-                        let avance = raw_pct.clamp(0.0, 100.0);
-
-                        tiempo_restante = if stat.acc_cpu_seconds >= tiempo_total {
-                            0.0
-                        } else {
-                            (tiempo_total - stat.acc_cpu_seconds).max(0.0)
-                        };
-
-                        let interactividad = 1;
-
-                        return Proceso {
-                            pid: pid_u32.to_string(),
-                            nombre: process.name().to_string(),
-                            prioridad,
-                            tiempo_cpu: cpu,
-                            memoria: mem_kb,
-                            estado,
-                            interactividad,
-                            avance,
-                            iteraciones: stat.iteraciones,
-                            tiempo_total,
-                            tiempo_restante,
-                        };
-                    } else {
-                        let interactividad = 1;
-                        return Proceso {
-                            pid: pid_u32.to_string(),
-                            nombre: process.name().to_string(),
-                            prioridad,
-                            tiempo_cpu: cpu,
-                            memoria: mem_kb,
-                            estado,
-                            interactividad,
-                            avance: 0.0,
-                            iteraciones: stat.iteraciones,
-                            tiempo_total,
-                            tiempo_restante,
-                        };
+// `intervalo_max_ms` activa el ahorro de energía: sin él el intervalo es fijo
+#[tauri::command]
+fn iniciar_muestreo(
+    intervalo_ms: u64,
+    normalizar_cpu: Option<bool>,
+    intervalo_max_ms: Option<u64>,
+    app: AppHandle,
+) -> Result<(), ErrorSim> {
+    if intervalo_ms == 0 {
+        return Err(ErrorSim::EntradaInvalida(
+            "intervalo_ms debe ser mayor que 0".to_string(),
+        ));
+    }
+    let intervalo_max_ms = intervalo_max_ms.unwrap_or(intervalo_ms);
+    if intervalo_max_ms < intervalo_ms {
+        return Err(ErrorSim::EntradaInvalida(format!(
+            "intervalo_max_ms ({intervalo_max_ms}) no puede ser menor que intervalo_ms ({intervalo_ms})"
+        )));
+    }
+
+    let mut muestreador = MUESTREADOR.lock()?;
+    if muestreador.is_some() {
+        return Err(ErrorSim::EntradaInvalida(
+            "Ya hay un muestreo en curso".to_string(),
+        ));
+    }
+
+    CONFIG.lock()?.intervalo_esperado = intervalo_ms as f64 / 1000.0;
+
+    let detener = Arc::new(AtomicBool::new(false));
+    let bandera = Arc::clone(&detener);
+    let mut intervalo = IntervaloAdaptativo::nuevo(
+        Duration::from_millis(intervalo_ms),
+        Duration::from_millis(intervalo_max_ms),
+    );
+
+    let hilo = thread::spawn(move || {
+        let mut estados = HashMap::new();
+        let mut espera = intervalo.minimo;
+        while !bandera.load(Ordering::SeqCst) {
+            match snapshot(normalizar_cpu) {
+                Ok(instantanea) => {
+                    let nueva = intervalo.registrar(muestra_ociosa(&instantanea, &mut estados));
+                    // El acumulado de CPU recorta el elapsed a 2 intervalos esperados:
+                    // sin actualizarlo, un intervalo estirado perdería CPU
+                    if nueva != espera {
+                        espera = nueva;
+                        if let Ok(mut cfg) = CONFIG.lock() {
+                            cfg.intervalo_esperado = espera.as_secs_f64();
+                        }
                     }
-                } else {
-                    let initial_iter = if cpu > CPU_THRESHOLD { 1 } else { 0 };
-                    let stat = ProcStat {
-                        last_seen: now,
-                        acc_cpu_seconds: 0.0,
-                        iteraciones: initial_iter,
-                        last_cpu_positive: cpu > CPU_THRESHOLD,
-                        ewma_cpu: cpu,
-                    };
-                    map.insert(pid_u32, stat);
-
-                    let interactividad = 1;
-                    return Proceso {
-                        pid: pid_u32.to_string(),
-                        nombre: process.name().to_string(),
-                        prioridad,
-                        tiempo_cpu: cpu,
-                        memoria: mem_kb,
-                        estado,
-                        interactividad,
-                        avance: 0.0,
-                        iteraciones: initial_iter,
-                        tiempo_total,
-                        tiempo_restante,
-                    };
+                    emitir_eventos(&instantanea, &app);
+                    let _ = app.emit("procesos_actualizados", &instantanea.procesos);
                 }
+                Err(e) => eprintln!("muestreo en segundo plano fallido: {e}"),
             }
-        })
-        .collect();
-
-    // Limpieza de procesos eliminados
-    {
-        let current_pids: std::collections::HashSet<u32> =
-            sys.processes().keys().map(|pid| pid.as_u32()).collect();
-        let mut map = PROC_STATE
-            .lock()
-            .expect("failed to lock PROC_STATE mutex for cleanup");
-        let stale: Vec<u32> = map
-            .keys()
-            .cloned()
-            .filter(|k| !current_pids.contains(k))
-            .collect();
-        for k in stale {
-            map.remove(&k);
+            // detener_muestreo despierta al hilo para no esperar el intervalo completo
+            thread::park_timeout(espera);
         }
+    });
+
+    *muestreador = Some(Muestreador { detener, hilo });
+    Ok(())
+}
+
+#[tauri::command]
+fn detener_muestreo() -> Result<(), ErrorSim> {
+    let muestreador = MUESTREADOR.lock()?.take();
+    if let Some(Muestreador { detener, hilo }) = muestreador {
+        detener.store(true, Ordering::SeqCst);
+        hilo.thread().unpark();
+        let _ = hilo.join();
     }
+    Ok(())
+}
 
-    out.sort_by(|a, b| {
-        b.tiempo_cpu
-            .partial_cmp(&a.tiempo_cpu)
-            .unwrap_or(std::cmp::Ordering::Equal)
+// ----- Exportación -----
+// Toma una muestra nueva y la escribe como CSV; devuelve la cantidad de filas
+#[tauri::command]
+fn exportar_csv(ruta: String, app: AppHandle) -> Result<usize, ErrorSim> {
+    let instantanea = snapshot(None)?;
+    emitir_eventos(&instantanea, &app);
+    Ok(exportar::escribir_csv(&instantanea.procesos, &ruta)?)
+}
+
+// Muestra actual en formato de texto de Prometheus, para exponerla a un recolector
+#[tauri::command]
+fn metricas_prometheus(app: AppHandle) -> Result<String, ErrorSim> {
+    let instantanea = tomar_instantanea(None)?;
+    emitir_eventos(&instantanea, &app);
+    let (cpu, usada, total) = {
+        let mut sys = SYSTEM.lock()?;
+        sys.refresh_memory();
+        (
+            sys.global_cpu_info().cpu_usage() as f64,
+            sys.used_memory() as f64,
+            sys.total_memory() as f64,
+        )
+    };
+    let globales = [
+        ("sistema_cpu", "Uso total de CPU en porcentaje", cpu),
+        ("sistema_memoria_usada_bytes", "Memoria en uso", usada),
+        ("sistema_memoria_total_bytes", "Memoria total", total),
+        (
+            "sistema_procesos",
+            "Cantidad de procesos en la muestra",
+            instantanea.procesos.len() as f64,
+        ),
+    ];
+    Ok(exportar::metricas_prometheus(
+        &globales,
+        &instantanea.procesos,
+    ))
+}
+
+// ----- Árbol de procesos -----
+#[tauri::command]
+fn obtener_arbol_procesos(
+    normalizar_cpu: Option<bool>,
+    app: AppHandle,
+) -> Result<Vec<NodoProceso>, ErrorSim> {
+    let instantanea = tomar_instantanea(normalizar_cpu)?;
+    emitir_eventos(&instantanea, &app);
+    Ok(construir_arbol(instantanea.procesos))
+}
+
+// ----- Procesos simulados -----
+// Devuelve el PID asignado
+#[tauri::command]
+fn agregar_proceso_simulado(
+    nombre: String,
+    cpu: f64,
+    memoria_bytes: u64,
+    tiempo_total: f64,
+) -> Result<u32, ErrorSim> {
+    if !(cpu.is_finite() && cpu >= 0.0) {
+        return Err(ErrorSim::EntradaInvalida(format!(
+            "La CPU simulada debe ser un número no negativo, se recibió {cpu}"
+        )));
+    }
+    if !(tiempo_total.is_finite() && tiempo_total > 0.0) {
+        return Err(ErrorSim::EntradaInvalida(format!(
+            "El tiempo total debe ser positivo, se recibió {tiempo_total}"
+        )));
+    }
+    let pid = SIGUIENTE_PID_SIMULADO.fetch_add(1, Ordering::Relaxed);
+    let ahora = Instant::now();
+    SIMULADOS.lock()?.push(ProcesoSimulado {
+        pid,
+        nombre,
+        cpu,
+        memoria_bytes,
+        tiempo_total,
+        acc_cpu_seconds: 0.0,
+        creado: ahora,
+        creado_epoch: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        last_seen: ahora,
+        historial_cpu: VecDeque::with_capacity(HISTORIAL_CPU),
     });
+    Ok(pid)
+}
+
+#[tauri::command]
+fn limpiar_simulados() -> Result<(), ErrorSim> {
+    SIMULADOS.lock()?.clear();
+    Ok(())
+}
+
+// ----- Capturas con nombre para comparar más tarde -----
+// Guarda la muestra actual con `nombre`, reemplazando una captura anterior homónima;
+// devuelve la cantidad de procesos capturados
+#[tauri::command]
+fn capturar_instantanea(nombre: String, app: AppHandle) -> Result<usize, ErrorSim> {
+    let instantanea = tomar_instantanea(None)?;
+    emitir_eventos(&instantanea, &app);
+    guardar_captura(nombre, instantanea.procesos)
+}
+
+#[tauri::command]
+fn comparar_instantanea(nombre: String, app: AppHandle) -> Result<DiffInstantanea, ErrorSim> {
+    // Se copia la captura antes de muestrear para no retener CAPTURAS mientras tanto
+    let anteriores = procesos_capturados(&nombre)?;
+    let instantanea = tomar_instantanea(None)?;
+    emitir_eventos(&instantanea, &app);
+    Ok(diferenciar(anteriores, instantanea.procesos))
+}
+
+// ----- Variación entre las dos últimas muestras -----
+// No toma una muestra nueva: compara las dos últimas ya tomadas
+#[tauri::command]
+fn obtener_deltas() -> Result<Vec<DeltaProceso>, ErrorSim> {
+    deltas_ultimas_muestras()
+}
+
+// ----- Agrupación por aplicación -----
+#[tauri::command]
+fn obtener_grupos(
+    normalizar_cpu: Option<bool>,
+    app: AppHandle,
+) -> Result<Vec<GrupoProceso>, ErrorSim> {
+    let instantanea = tomar_instantanea(normalizar_cpu)?;
+    emitir_eventos(&instantanea, &app);
+    Ok(agrupar_por_nombre(&instantanea.procesos))
+}
+
+// Refresca un único PID, conservando sus acumuladores de ProcStat
+#[tauri::command]
+fn obtener_proceso(pid: u32, normalizar_cpu: Option<bool>) -> Result<Proceso, ErrorSim> {
+    let mut sys = SYSTEM.lock()?;
+    let pid_sys = Pid::from_u32(pid);
+    // refresh_process deja la entrada vieja si el PID ya no existe
+    if !sys.refresh_process(pid_sys) {
+        return Err(ErrorSim::ProcesoNoEncontrado(pid));
+    }
+
+    let muestra = Muestra::nueva(&sys, normalizar_cpu)?;
+    let process = sys
+        .process(pid_sys)
+        .ok_or(ErrorSim::ProcesoNoEncontrado(pid))?;
 
-    Ok(out)
+    let mut map = estado_procesos();
+    let (proceso, stat) = muestrear_proceso(pid, process, &muestra, map.get(&pid));
+    map.insert(pid, stat);
+    Ok(proceso)
+}
+
+// PID dueño de la ventana con el foco, para resaltar su fila; None si la plataforma
+// no lo permite averiguar
+#[tauri::command]
+fn proceso_primer_plano() -> Option<u32> {
+    plataforma::proceso_primer_plano()
+}
+
+// Los privilegios no cambian durante la sesión: se consultan una sola vez
+static PRIVILEGIOS_ELEVADOS: Lazy<bool> = Lazy::new(plataforma::privilegios_elevados);
+
+// Sin ellos, terminar/suspender/repriorizar procesos de otros usuarios fallará con
+// `permiso`; la interfaz puede deshabilitar esas acciones de antemano
+#[tauri::command]
+fn privilegios_elevados() -> bool {
+    *PRIVILEGIOS_ELEVADOS
+}
+
+// Fila de esta misma app, para mostrar su propio consumo sin buscarla en la lista
+#[tauri::command]
+fn uso_propio(normalizar_cpu: Option<bool>) -> Result<Proceso, ErrorSim> {
+    obtener_proceso(std::process::id(), normalizar_cpu)
+}
+
+// ----- Descriptores abiertos -----
+// Un total que crece entre consultas delata un proceso que pierde handles
+#[derive(Serialize)]
+pub struct Descriptores {
+    pub pid: u32,
+    // Descriptores de archivo en Linux, handles en Windows
+    pub abiertos: u32,
+    // None si la plataforma no distingue los sockets
+    pub sockets: Option<u32>,
+}
+
+#[tauri::command]
+fn recuentos_descriptores(pid: u32) -> Result<Descriptores, ErrorSim> {
+    verificar_proceso(pid)?;
+    let (abiertos, sockets) = plataforma::descriptores(pid).map_err(|e| {
+        ErrorSim::io(
+            e,
+            &format!("No se pudieron contar los descriptores del proceso {pid}"),
+        )
+    })?;
+    Ok(Descriptores {
+        pid,
+        abiertos,
+        sockets,
+    })
 }
 
 // ----- Estructura para información del sistema -----
@@ -204,21 +631,801 @@ pub struct SystemInfo {
 }
 
 #[tauri::command]
-fn obtener_info_sistema() -> SystemInfo {
-    let mut sys = System::new_all();
+fn obtener_info_sistema() -> Result<SystemInfo, ErrorSim> {
+    let mut sys = SYSTEM.lock()?;
     sys.refresh_memory();
-    SystemInfo {
+    Ok(SystemInfo {
         total_memory: sys.total_memory(),
         used_memory: sys.used_memory(),
         total_swap: sys.total_swap(),
         used_swap: sys.used_swap(),
+    })
+}
+
+// ----- Resumen global del sistema -----
+#[derive(Serialize)]
+pub struct CargaPromedio {
+    pub uno: f64,
+    pub cinco: f64,
+    pub quince: f64,
+}
+
+#[derive(Serialize)]
+pub struct ResumenSistema {
+    // Uso total de CPU (0-100, promedio de todos los núcleos)
+    pub cpu_total: f64,
+    pub memoria_total: u64,
+    pub memoria_usada: u64,
+    pub swap_total: u64,
+    pub swap_usado: u64,
+    pub carga: CargaPromedio,
+    // Segundos desde el arranque
+    pub uptime: u64,
+    pub cantidad_procesos: usize,
+}
+
+#[tauri::command]
+fn obtener_resumen_sistema() -> Result<ResumenSistema, ErrorSim> {
+    let mut sys = SYSTEM.lock()?;
+    sys.refresh_cpu();
+    sys.refresh_memory();
+    let carga = sys.load_average();
+    Ok(ResumenSistema {
+        cpu_total: sys.global_cpu_info().cpu_usage() as f64,
+        memoria_total: sys.total_memory(),
+        memoria_usada: sys.used_memory(),
+        swap_total: sys.total_swap(),
+        swap_usado: sys.used_swap(),
+        carga: CargaPromedio {
+            uno: carga.one,
+            cinco: carga.five,
+            quince: carga.fifteen,
+        },
+        uptime: sys.uptime(),
+        // Se cuenta la lista del último muestreo: refrescar los procesos aquí acortaría
+        // el intervalo con el que sysinfo calcula su CPU en obtener_procesos
+        cantidad_procesos: sys.processes().len(),
+    })
+}
+
+// Indicador barato para consultar seguido: (CPU global %, memoria usada, memoria
+// total). No toca la lista de procesos, que se sigue refrescando con obtener_procesos.
+#[tauri::command]
+fn latido_sistema() -> Result<(f64, u64, u64), ErrorSim> {
+    let mut sys = SYSTEM.lock()?;
+    sys.refresh_cpu();
+    sys.refresh_memory();
+    Ok((
+        sys.global_cpu_info().cpu_usage() as f64,
+        sys.used_memory(),
+        sys.total_memory(),
+    ))
+}
+
+// ----- Sensores de temperatura -----
+#[derive(Serialize)]
+pub struct ComponenteTermico {
+    pub etiqueta: String,
+    // Grados Celsius; None si el sensor no pudo leerse
+    pub temperatura: Option<f64>,
+    pub maxima: Option<f64>,
+    pub critica: Option<f64>,
+}
+
+// Lista vacía en equipos o plataformas sin sensores expuestos
+#[tauri::command]
+fn obtener_componentes() -> Result<Vec<ComponenteTermico>, ErrorSim> {
+    let mut sys = SYSTEM.lock()?;
+    // La lista de sensores se arma una vez; después solo se releen las temperaturas
+    if sys.components().is_empty() {
+        sys.refresh_components_list();
+    } else {
+        sys.refresh_components();
     }
+    let lectura = |t: f32| Some(t as f64).filter(|t| t.is_finite());
+    Ok(sys
+        .components()
+        .iter()
+        .map(|c| ComponenteTermico {
+            etiqueta: c.label().to_string(),
+            temperatura: lectura(c.temperature()),
+            maxima: lectura(c.max()),
+            critica: c.critical().and_then(lectura),
+        })
+        .collect())
+}
+
+// ----- Tráfico de red -----
+#[derive(Serialize)]
+pub struct InterfazRed {
+    pub nombre: String,
+    // Bytes desde la consulta anterior
+    pub recibidos: u64,
+    pub transmitidos: u64,
+    // Bytes por segundo en ese intervalo; 0 en la primera consulta
+    pub recibidos_por_segundo: f64,
+    pub transmitidos_por_segundo: f64,
+    // Totales desde que arrancó la interfaz
+    pub recibidos_total: u64,
+    pub transmitidos_total: u64,
+}
+
+// Instante de la consulta anterior, para convertir los deltas de sysinfo en tasas
+static ULTIMA_CONSULTA_RED: Lazy<Mutex<Option<Instant>>> = Lazy::new(|| Mutex::new(None));
+
+#[tauri::command]
+fn obtener_red() -> Result<Vec<InterfazRed>, ErrorSim> {
+    let mut sys = SYSTEM.lock()?;
+    // Las redes viven dentro de SYSTEM, así que los deltas son entre consultas a este
+    // comando. Rearmar la lista también actualiza los contadores y detecta interfaces
+    // nuevas (VPN, USB...); refrescar después dejaría los deltas en cero.
+    sys.refresh_networks_list();
+    let ahora = Instant::now();
+    let segundos = ULTIMA_CONSULTA_RED
+        .lock()?
+        .replace(ahora)
+        .map(|antes| ahora.duration_since(antes).as_secs_f64())
+        .filter(|&s| s > 0.0);
+    let tasa = |bytes: u64| segundos.map_or(0.0, |s| bytes as f64 / s);
+
+    let mut interfaces: Vec<InterfazRed> = sys
+        .networks()
+        .iter()
+        .map(|(nombre, datos)| InterfazRed {
+            nombre: nombre.clone(),
+            recibidos: datos.received(),
+            transmitidos: datos.transmitted(),
+            recibidos_por_segundo: tasa(datos.received()),
+            transmitidos_por_segundo: tasa(datos.transmitted()),
+            recibidos_total: datos.total_received(),
+            transmitidos_total: datos.total_transmitted(),
+        })
+        .collect();
+    interfaces.sort_by(|a, b| a.nombre.cmp(&b.nombre));
+    Ok(interfaces)
+}
+
+// Uso de cada núcleo lógico en porcentaje
+#[tauri::command]
+fn obtener_cpu_por_nucleo() -> Result<Vec<f64>, ErrorSim> {
+    let primera_lectura = {
+        let mut sys = SYSTEM.lock()?;
+        sys.refresh_cpu();
+        sys.cpus().iter().all(|c| c.cpu_usage() == 0.0)
+    };
+
+    // En la primera lectura aún no hay delta y sysinfo reporta 0 en todos los
+    // núcleos: se espera el intervalo mínimo y se vuelve a medir. La espera va sin
+    // el candado para no frenar al muestreo de procesos.
+    if primera_lectura {
+        std::thread::sleep(System::MINIMUM_CPU_UPDATE_INTERVAL);
+    }
+
+    let mut sys = SYSTEM.lock()?;
+    if primera_lectura {
+        sys.refresh_cpu();
+    }
+    Ok(sys.cpus().iter().map(|c| c.cpu_usage() as f64).collect())
+}
+
+// ----- Gestión de procesos -----
+#[tauri::command]
+fn matar_proceso(pid: u32) -> Result<(), ErrorSim> {
+    let mut sys = SYSTEM.lock()?;
+    let pid_sys = Pid::from_u32(pid);
+    if !sys.refresh_process(pid_sys) {
+        return Err(ErrorSim::ProcesoNoEncontrado(pid));
+    }
+    let process = sys
+        .process(pid_sys)
+        .ok_or(ErrorSim::ProcesoNoEncontrado(pid))?;
+    if CONFIG.lock()?.proteccion.protege(pid, process.name()) {
+        return Err(ErrorSim::Protegido(pid));
+    }
+
+    if !process.kill() {
+        // kill() solo informa éxito o fracaso; la causa (p. ej. permisos) queda en errno
+        return Err(ErrorSim::io(
+            std::io::Error::last_os_error(),
+            &format!("No se pudo terminar el proceso {pid}"),
+        ));
+    }
+
+    estado_procesos().remove(&pid);
+    Ok(())
+}
+
+#[tauri::command]
+fn suspender_proceso(pid: u32) -> Result<(), ErrorSim> {
+    verificar_no_protegido(pid)?;
+    plataforma::suspender(pid)
+        .map_err(|e| ErrorSim::io(e, &format!("No se pudo suspender el proceso {pid}")))
+}
+
+#[tauri::command]
+fn reanudar_proceso(pid: u32) -> Result<(), ErrorSim> {
+    verificar_proceso(pid)?;
+    plataforma::reanudar(pid)
+        .map_err(|e| ErrorSim::io(e, &format!("No se pudo reanudar el proceso {pid}")))
+}
+
+// Cambia el valor nice (-20 a 19, menor = más prioritario). Bajarlo de 0 suele
+// requerir privilegios; en ese caso el error es ErrorSim::Permiso.
+#[tauri::command]
+fn establecer_prioridad(pid: u32, prioridad: i32) -> Result<(), ErrorSim> {
+    if !(-20..=19).contains(&prioridad) {
+        return Err(ErrorSim::EntradaInvalida(format!(
+            "La prioridad debe estar entre -20 y 19, se recibió {prioridad}"
+        )));
+    }
+    verificar_proceso(pid)?;
+    // El próximo muestreo la relee del sistema, así que no hay caché que actualizar
+    plataforma::establecer_prioridad(pid, prioridad).map_err(|e| {
+        ErrorSim::io(
+            e,
+            &format!("No se pudo cambiar la prioridad del proceso {pid}"),
+        )
+    })
+}
+
+// Restringe el proceso a los núcleos indicados (índices desde 0)
+#[tauri::command]
+fn establecer_afinidad(pid: u32, cores: Vec<usize>) -> Result<(), ErrorSim> {
+    let nucleos = SYSTEM.lock()?.cpus().len();
+    if cores.is_empty() {
+        return Err(ErrorSim::EntradaInvalida(
+            "Se debe indicar al menos un núcleo".to_string(),
+        ));
+    }
+    if let Some(&fuera) = cores.iter().find(|&&c| c >= nucleos) {
+        return Err(ErrorSim::EntradaInvalida(format!(
+            "El núcleo {fuera} no existe: el equipo tiene {nucleos}"
+        )));
+    }
+    verificar_proceso(pid)?;
+    plataforma::establecer_afinidad(pid, &cores).map_err(|e| {
+        ErrorSim::io(
+            e,
+            &format!("No se pudo cambiar la afinidad del proceso {pid}"),
+        )
+    })
+}
+
+fn verificar_proceso(pid: u32) -> Result<(), ErrorSim> {
+    let mut sys = SYSTEM.lock()?;
+    if sys.refresh_process(Pid::from_u32(pid)) {
+        Ok(())
+    } else {
+        Err(ErrorSim::ProcesoNoEncontrado(pid))
+    }
+}
+
+fn verificar_no_protegido(pid: u32) -> Result<(), ErrorSim> {
+    let mut sys = SYSTEM.lock()?;
+    let pid_sys = Pid::from_u32(pid);
+    if !sys.refresh_process(pid_sys) {
+        return Err(ErrorSim::ProcesoNoEncontrado(pid));
+    }
+    let process = sys
+        .process(pid_sys)
+        .ok_or(ErrorSim::ProcesoNoEncontrado(pid))?;
+    if CONFIG.lock()?.proteccion.protege(pid, process.name()) {
+        return Err(ErrorSim::Protegido(pid));
+    }
+    Ok(())
+}
+
+// ----- Simulación de planificación -----
+#[tauri::command]
+fn simular_fifo(procesos: Vec<ProcesoEntrada>) -> Result<ResultadoSimulacion, ErrorSim> {
+    planificador::validar_entrada(&procesos)?;
+    Ok(planificador::fifo(&procesos))
+}
+
+#[tauri::command]
+fn simular_round_robin(
+    procesos: Vec<ProcesoEntrada>,
+    quantum: f64,
+    costo_cambio_contexto: Option<f64>,
+) -> Result<ResultadoSimulacion, ErrorSim> {
+    planificador::validar_entrada(&procesos)?;
+    let quantum = planificador::validar_quantum(quantum)?;
+    Ok(planificador::round_robin(
+        &procesos,
+        quantum,
+        costo_cambio_contexto.unwrap_or(0.0),
+    ))
+}
+
+// E/S compartiendo un único dispositivo FIFO; sin quantum el planificador es FIFO
+#[tauri::command]
+fn simular_con_dispositivo_io(
+    procesos: Vec<ProcesoEntrada>,
+    quantum: Option<f64>,
+    costo_cambio_contexto: Option<f64>,
+) -> Result<ResultadoSimulacion, ErrorSim> {
+    planificador::validar_entrada(&procesos)?;
+    let quantum = quantum.map(planificador::validar_quantum).transpose()?;
+    Ok(planificador::con_dispositivo_io(
+        &procesos,
+        quantum,
+        costo_cambio_contexto.unwrap_or(0.0),
+    ))
+}
+
+#[tauri::command]
+fn simular_fifo_prioridad(procesos: Vec<ProcesoEntrada>) -> Result<ResultadoSimulacion, ErrorSim> {
+    planificador::validar_entrada(&procesos)?;
+    planificador::validar_sin_io(&procesos, "FIFO con prioridad")?;
+    Ok(planificador::fifo_prioridad(&procesos))
+}
+
+#[tauri::command]
+fn simular_sjf(procesos: Vec<ProcesoEntrada>) -> Result<ResultadoSimulacion, ErrorSim> {
+    planificador::validar_entrada(&procesos)?;
+    planificador::validar_sin_io(&procesos, "SJF")?;
+    Ok(planificador::sjf(&procesos))
+}
+
+#[tauri::command]
+fn simular_ljf(procesos: Vec<ProcesoEntrada>) -> Result<ResultadoSimulacion, ErrorSim> {
+    planificador::validar_entrada(&procesos)?;
+    planificador::validar_sin_io(&procesos, "LJF")?;
+    Ok(planificador::ljf(&procesos))
+}
+
+#[tauri::command]
+fn simular_srtf(
+    procesos: Vec<ProcesoEntrada>,
+    costo_cambio_contexto: Option<f64>,
+) -> Result<ResultadoSimulacion, ErrorSim> {
+    planificador::validar_entrada(&procesos)?;
+    planificador::validar_sin_io(&procesos, "SRTF")?;
+    Ok(planificador::srtf(
+        &procesos,
+        costo_cambio_contexto.unwrap_or(0.0),
+    ))
+}
+
+#[tauri::command]
+fn simular_prioridad(
+    procesos: Vec<ProcesoEntrada>,
+    preemptivo: bool,
+    envejecimiento: Option<f64>,
+    costo_cambio_contexto: Option<f64>,
+) -> Result<ResultadoSimulacion, ErrorSim> {
+    planificador::validar_entrada(&procesos)?;
+    planificador::validar_sin_io(&procesos, "La planificación por prioridad")?;
+    Ok(planificador::prioridad(
+        &procesos,
+        preemptivo,
+        envejecimiento,
+        costo_cambio_contexto.unwrap_or(0.0),
+    ))
+}
+
+#[tauri::command]
+fn simular_mlfq(
+    procesos: Vec<ProcesoEntrada>,
+    quantums: Vec<f64>,
+    periodo_boost: Option<f64>,
+    costo_cambio_contexto: Option<f64>,
+) -> Result<ResultadoSimulacion, ErrorSim> {
+    planificador::validar_entrada(&procesos)?;
+    planificador::validar_sin_io(&procesos, "MLFQ")?;
+    if quantums.is_empty() {
+        return Err(ErrorSim::EntradaInvalida(
+            "MLFQ necesita al menos un quantum".to_string(),
+        ));
+    }
+    for &q in &quantums {
+        planificador::validar_quantum(q)?;
+    }
+    Ok(planificador::mlfq(
+        &procesos,
+        &quantums,
+        periodo_boost,
+        costo_cambio_contexto.unwrap_or(0.0),
+    ))
+}
+
+// Tope para no generar listas que la interfaz no podría dibujar
+const MAX_PROCESOS_DEMO: usize = 1000;
+
+#[tauri::command]
+fn generar_procesos_demo(n: usize, semilla: u64) -> Result<Vec<ProcesoEntrada>, ErrorSim> {
+    if n > MAX_PROCESOS_DEMO {
+        return Err(ErrorSim::EntradaInvalida(format!(
+            "Se pueden generar hasta {MAX_PROCESOS_DEMO} procesos, se pidieron {n}"
+        )));
+    }
+    Ok(planificador::procesos_demo(n, semilla))
+}
+
+// Ancho predeterminado del Gantt en texto, el de una terminal clásica
+const COLUMNAS_GANTT_ASCII: usize = 80;
+
+// Gantt de un resultado ya calculado como texto, para logs y copiar/pegar
+#[tauri::command]
+fn gantt_ascii(resultado: ResultadoSimulacion, max_columnas: Option<usize>) -> String {
+    resultado.a_ascii(max_columnas.unwrap_or(COLUMNAS_GANTT_ASCII))
+}
+
+// Histograma de las ráfagas ingresadas, para la pantalla de carga
+#[tauri::command]
+fn distribucion_bursts(
+    procesos: Vec<ProcesoEntrada>,
+    num_buckets: usize,
+) -> Result<Vec<(f64, f64, usize)>, ErrorSim> {
+    if num_buckets == 0 {
+        return Err(ErrorSim::EntradaInvalida(
+            "num_buckets debe ser mayor que 0".to_string(),
+        ));
+    }
+    Ok(planificador::distribucion_bursts(&procesos, num_buckets))
+}
+
+// ----- Entradas del simulador a partir de procesos reales -----
+#[tauri::command]
+fn estimar_entradas_desde_reales(pids: Vec<u32>) -> Result<Vec<ProcesoEntrada>, ErrorSim> {
+    estimar_entradas(pids)
+}
+
+// Conjunto predefinido para mostrar el efecto convoy con comparar_algoritmos
+#[tauri::command]
+fn caso_convoy() -> Vec<ProcesoEntrada> {
+    planificador::caso_convoy()
+}
+
+// Corre la misma entrada por cada algoritmo pedido, para compararlos lado a lado
+#[tauri::command]
+fn comparar_algoritmos(
+    procesos: Vec<ProcesoEntrada>,
+    algoritmos: Vec<Algoritmo>,
+    quantum: Option<f64>,
+    costo_cambio_contexto: Option<f64>,
+) -> Result<Vec<(Algoritmo, ResultadoSimulacion)>, ErrorSim> {
+    planificador::comparar(
+        &procesos,
+        algoritmos,
+        quantum,
+        costo_cambio_contexto.unwrap_or(0.0),
+    )
+}
+
+// ----- Simulación paso a paso -----
+type SimId = u32;
+
+static SIMULACIONES: Lazy<Mutex<HashMap<SimId, Simulador>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static SIGUIENTE_SIM: AtomicU32 = AtomicU32::new(1);
+
+#[tauri::command]
+fn crear_simulacion(
+    procesos: Vec<ProcesoEntrada>,
+    algoritmo: Algoritmo,
+    quantum: Option<f64>,
+) -> Result<SimId, ErrorSim> {
+    planificador::validar_entrada(&procesos)?;
+    // Simulador solo reparte CPU, ni siquiera con FIFO o Round Robin
+    planificador::validar_sin_io(&procesos, "La simulación paso a paso")?;
+    let quantum = planificador::quantum_para(&[algoritmo], quantum)?;
+    let id = SIGUIENTE_SIM.fetch_add(1, Ordering::Relaxed);
+    SIMULACIONES
+        .lock()?
+        .insert(id, Simulador::nuevo(procesos, algoritmo, quantum));
+    Ok(id)
+}
+
+fn simulacion_inexistente(sim: SimId) -> ErrorSim {
+    ErrorSim::EntradaInvalida(format!("No existe la simulación {sim}"))
+}
+
+#[tauri::command]
+fn avanzar_tick(sim: SimId) -> Result<EstadoSimulacion, ErrorSim> {
+    SIMULACIONES
+        .lock()?
+        .get_mut(&sim)
+        .map(Simulador::avanzar_tick)
+        .ok_or_else(|| simulacion_inexistente(sim))
+}
+
+#[tauri::command]
+fn estado_simulacion(sim: SimId) -> Result<EstadoSimulacion, ErrorSim> {
+    SIMULACIONES
+        .lock()?
+        .get(&sim)
+        .map(Simulador::estado)
+        .ok_or_else(|| simulacion_inexistente(sim))
+}
+
+// Libera una simulación que la interfaz ya no va a usar
+#[tauri::command]
+fn descartar_simulacion(sim: SimId) -> Result<bool, ErrorSim> {
+    // Sin detener antes el reloj, su hilo terminaría solo al no encontrarla
+    if let Some(mut reloj) = RELOJES.lock()?.remove(&sim) {
+        reloj.pausar();
+    }
+    Ok(SIMULACIONES.lock()?.remove(&sim).is_some())
+}
+
+// ----- Reproducción automática (reloj simulado) -----
+// Intervalo real entre ticks a velocidad 1x, y el mínimo para no saturar la interfaz
+const INTERVALO_TICK: Duration = Duration::from_millis(1000);
+const INTERVALO_TICK_MIN: Duration = Duration::from_millis(10);
+
+struct Reloj {
+    // Microsegundos entre ticks; el hilo lo relee en cada vuelta
+    intervalo_us: Arc<AtomicU64>,
+    // Presente mientras se reproduce
+    hilo: Option<(Arc<AtomicBool>, JoinHandle<()>)>,
+}
+
+impl Reloj {
+    fn nuevo() -> Self {
+        Reloj {
+            intervalo_us: Arc::new(AtomicU64::new(INTERVALO_TICK.as_micros() as u64)),
+            hilo: None,
+        }
+    }
+
+    // Se llama con RELOJES tomado: el hilo solo usa SIMULACIONES, así que unirlo no
+    // puede interbloquear y nadie reanuda el reloj mientras tanto
+    fn pausar(&mut self) {
+        if let Some((detener, hilo)) = self.hilo.take() {
+            detener.store(true, Ordering::SeqCst);
+            hilo.thread().unpark();
+            let _ = hilo.join();
+        }
+    }
+}
+
+// La velocidad se conserva entre pausas; se olvida al descartar la simulación
+static RELOJES: Lazy<Mutex<HashMap<SimId, Reloj>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Serialize)]
+pub struct TickSimulacion {
+    pub sim: SimId,
+    pub estado: EstadoSimulacion,
+}
+
+// Avanza un tick por intervalo y emite "tick_simulacion"; se detiene sola al terminar
+// la simulación. Llamarla mientras ya se reproduce no hace nada.
+#[tauri::command]
+fn reloj_play(sim: SimId, app: AppHandle) -> Result<(), ErrorSim> {
+    if !SIMULACIONES.lock()?.contains_key(&sim) {
+        return Err(simulacion_inexistente(sim));
+    }
+    let mut relojes = RELOJES.lock()?;
+    let reloj = relojes.entry(sim).or_insert_with(Reloj::nuevo);
+    if reloj
+        .hilo
+        .as_ref()
+        .is_some_and(|(_, hilo)| !hilo.is_finished())
+    {
+        return Ok(());
+    }
+
+    let detener = Arc::new(AtomicBool::new(false));
+    let bandera = Arc::clone(&detener);
+    let intervalo_us = Arc::clone(&reloj.intervalo_us);
+    let hilo = thread::spawn(move || {
+        while !bandera.load(Ordering::SeqCst) {
+            // El mutex se suelta antes de emitir
+            let estado = match SIMULACIONES.lock() {
+                Ok(mut sims) => match sims.get_mut(&sim) {
+                    Some(s) => s.avanzar_tick(),
+                    None => break,
+                },
+                Err(_) => break,
+            };
+            let terminada = estado.terminada;
+            let _ = app.emit("tick_simulacion", &TickSimulacion { sim, estado });
+            if terminada {
+                break;
+            }
+            // reloj_pausa despierta al hilo para no esperar el intervalo completo
+            thread::park_timeout(Duration::from_micros(intervalo_us.load(Ordering::Relaxed)));
+        }
+    });
+    reloj.hilo = Some((detener, hilo));
+    Ok(())
+}
+
+// Espera a que el hilo termine, así no llega ningún tick después de pausar
+#[tauri::command]
+fn reloj_pausa(sim: SimId) -> Result<(), ErrorSim> {
+    if let Some(reloj) = RELOJES.lock()?.get_mut(&sim) {
+        reloj.pausar();
+    }
+    Ok(())
+}
+
+// `multiplicador` 2 es el doble de rápido (medio segundo entre ticks); se aplica
+// desde el próximo tick, también si se cambia durante la reproducción
+#[tauri::command]
+fn reloj_velocidad(sim: SimId, multiplicador: f64) -> Result<(), ErrorSim> {
+    if !(multiplicador.is_finite() && multiplicador > 0.0) {
+        return Err(ErrorSim::EntradaInvalida(format!(
+            "El multiplicador debe ser positivo, se recibió {multiplicador}"
+        )));
+    }
+    if !SIMULACIONES.lock()?.contains_key(&sim) {
+        return Err(simulacion_inexistente(sim));
+    }
+    let intervalo = INTERVALO_TICK
+        .div_f64(multiplicador)
+        .max(INTERVALO_TICK_MIN);
+    RELOJES
+        .lock()?
+        .entry(sim)
+        .or_insert_with(Reloj::nuevo)
+        .intervalo_us
+        .store(intervalo.as_micros() as u64, Ordering::Relaxed);
+    Ok(())
+}
+
+// Guarda una simulación (métricas + Gantt) para compararla más tarde
+#[tauri::command]
+fn exportar_simulacion(resultado: ResultadoSimulacion, ruta: String) -> Result<(), ErrorSim> {
+    Ok(exportar::guardar_json(&resultado, &ruta)?)
+}
+
+#[tauri::command]
+fn importar_simulacion(ruta: String) -> Result<ResultadoSimulacion, ErrorSim> {
+    Ok(exportar::leer_json(&ruta)?)
 }
 
 fn main() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
-        .invoke_handler(tauri::generate_handler![obtener_procesos, obtener_info_sistema])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .invoke_handler(tauri::generate_handler![
+            obtener_procesos,
+            top_cpu,
+            perfil_muestreo,
+            vigilar_proceso,
+            dejar_de_vigilar,
+            fijar_proceso,
+            desfijar_proceso,
+            obtener_proceso,
+            uso_propio,
+            recuentos_descriptores,
+            privilegios_elevados,
+            proceso_primer_plano,
+            obtener_arbol_procesos,
+            obtener_grupos,
+            capturar_instantanea,
+            agregar_proceso_simulado,
+            limpiar_simulados,
+            comparar_instantanea,
+            obtener_deltas,
+            exportar_csv,
+            metricas_prometheus,
+            iniciar_muestreo,
+            detener_muestreo,
+            obtener_info_sistema,
+            obtener_cpu_por_nucleo,
+            obtener_resumen_sistema,
+            latido_sistema,
+            obtener_componentes,
+            obtener_red,
+            configurar_muestreo,
+            configurar_tiempo_total,
+            configurar_al_completar,
+            configurar_pico_cpu,
+            configurar_idioma,
+            configurar_max_procesos,
+            configurar_protegidos,
+            reiniciar_estado,
+            guardar_estado,
+            cargar_estado,
+            #[cfg(debug_assertions)]
+            volcar_estado_interno,
+            matar_proceso,
+            suspender_proceso,
+            reanudar_proceso,
+            establecer_prioridad,
+            establecer_afinidad,
+            simular_fifo,
+            simular_round_robin,
+            simular_con_dispositivo_io,
+            simular_fifo_prioridad,
+            simular_sjf,
+            simular_ljf,
+            simular_srtf,
+            simular_prioridad,
+            simular_mlfq,
+            comparar_algoritmos,
+            generar_procesos_demo,
+            caso_convoy,
+            estimar_entradas_desde_reales,
+            distribucion_bursts,
+            gantt_ascii,
+            crear_simulacion,
+            avanzar_tick,
+            estado_simulacion,
+            descartar_simulacion,
+            reloj_play,
+            reloj_pausa,
+            reloj_velocidad,
+            exportar_simulacion,
+            importar_simulacion
+        ])
+        .setup(|app| {
+            if let Err(e) = persistencia::cargar(app.handle()) {
+                eprintln!("no se pudo restaurar el estado de procesos: {e}");
+            }
+            Ok(())
+        })
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| match event {
+            // Se detiene el muestreo al pedirse el cierre para que no siga consumiendo
+            // CPU durante la salida; al salir se repite por si el pedido no pasó por aquí
+            RunEvent::ExitRequested { .. } => {
+                if let Err(e) = detener_muestreo() {
+                    eprintln!("no se pudo detener el muestreo: {e}");
+                }
+            }
+            RunEvent::Exit => {
+                if let Err(e) = detener_muestreo() {
+                    eprintln!("no se pudo detener el muestreo: {e}");
+                }
+                // Con el hilo ya unido, ninguna muestra modifica el estado mientras se guarda
+                if let Err(e) = persistencia::guardar(app) {
+                    eprintln!("no se pudo guardar el estado de procesos: {e}");
+                }
+            }
+            _ => {}
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simulador_fifo_lib::planificador::Rafaga;
+
+    fn entrada(pid: &str, llegada: f64, rafaga: f64) -> ProcesoEntrada {
+        ProcesoEntrada {
+            pid: pid.to_string(),
+            llegada,
+            rafaga,
+            prioridad: 0,
+            rafagas: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn los_algoritmos_sin_e_s_rechazan_rafagas_de_e_s() {
+        let con_io = vec![ProcesoEntrada {
+            rafagas: vec![Rafaga::Cpu(2.0), Rafaga::Io(1.0), Rafaga::Cpu(1.0)],
+            ..entrada("A", 0.0, 0.0)
+        }];
+        assert!(simular_sjf(con_io.clone()).is_err());
+        assert!(simular_srtf(con_io.clone(), None).is_err());
+        assert!(comparar_algoritmos(
+            con_io.clone(),
+            vec![Algoritmo::Fifo, Algoritmo::Sjf],
+            None,
+            None
+        )
+        .is_err());
+        assert!(comparar_algoritmos(con_io.clone(), vec![Algoritmo::Fifo], None, None).is_ok());
+        assert!(simular_fifo(con_io).is_ok());
+    }
+
+    #[test]
+    fn la_simulacion_paso_a_paso_rechaza_rafagas_de_e_s() {
+        let con_io = vec![ProcesoEntrada {
+            rafagas: vec![Rafaga::Cpu(2.0), Rafaga::Io(1.0), Rafaga::Cpu(1.0)],
+            ..entrada("A", 0.0, 0.0)
+        }];
+        for algoritmo in [Algoritmo::Fifo, Algoritmo::RoundRobin] {
+            assert!(matches!(
+                crear_simulacion(con_io.clone(), algoritmo, Some(1.0)),
+                Err(ErrorSim::EntradaInvalida(_))
+            ));
+        }
+        assert!(crear_simulacion(vec![entrada("A", 0.0, 3.0)], Algoritmo::Fifo, None).is_ok());
+    }
 }
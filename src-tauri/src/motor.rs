@@ -0,0 +1,1950 @@
+// ----- Motor de muestreo -----
+// Muestreo de procesos y consultas sobre la lista, sin depender de Tauri, para
+// reutilizarlos desde otra capa (p. ej. HTTP) o en pruebas. Los comandos de main.rs
+// solo agregan la emisión de eventos.
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use sysinfo::{CpuExt, Pid, PidExt, ProcessExt, ProcessStatus, System, SystemExt, Uid, UserExt};
+
+use crate::errores::ErrorSim;
+use crate::planificador::ProcesoEntrada;
+use crate::plataforma;
+
+// ----- Estructura que serializamos al frontend -----
+// También Deserialize, para que otro cliente (p. ej. uno HTTP) pueda leer el mismo JSON
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Proceso {
+    pub pid: String,
+    pub nombre: String,
+    // Explicación breve para nombres de sistema conocidos (svchost, kworker...)
+    pub descripcion: Option<String>,
+    pub ruta: String,
+    pub cmd: Vec<String>,
+    pub prioridad: i32,
+    pub tiempo_cpu: f64,
+    // Segundos de CPU consumidos desde la muestra anterior
+    pub delta_cpu_seconds: f64,
+    pub cpu_normalizado: f64,
+    pub cpu_suavizado: f64,
+    pub memoria: u64, // KB
+    pub memoria_bytes: u64,
+    pub memoria_humana: String,
+    // Espacio de direcciones virtual en bytes (incluye regiones mapeadas no residentes)
+    pub memoria_virtual: u64,
+    // Bytes en swap; 0 si la plataforma no lo informa
+    pub swap: u64,
+    pub estado: Estado,
+    // Código crudo cuando `estado` es desconocido
+    pub estado_codigo: Option<u32>,
+    // `estado` en el idioma configurado, para mostrar tal cual
+    pub estado_etiqueta: String,
+    pub interactividad: i32,
+    pub avance: f64,
+    pub iteraciones: u32,
+    pub tiempo_total: f64,
+    pub tiempo_restante: f64,
+    pub tiempo_inicio: u64,
+    pub tiempo_vida: f64,
+    // Segundos reales desde que arrancó, según el sistema operativo (no es heurística)
+    pub tiempo_ejecucion: u64,
+    pub ppid: Option<u32>,
+    // matar_proceso/suspender_proceso lo rechazan
+    pub protegido: bool,
+    // El proceso de esta misma app; su CPU sube con cada muestreo
+    pub es_propio: bool,
+    // Fijado con fijar_proceso: obtener_procesos lo devuelve primero
+    pub fijado: bool,
+    // Hilo del kernel: no tiene línea de comandos ni memoria de usuario propia
+    pub es_kernel: bool,
+    pub hilos: u32,
+    pub usuario: Option<String>,
+    // Núcleos permitidos; vacío si la plataforma no lo informa
+    pub afinidad: Vec<usize>,
+    pub historial_cpu: Vec<f64>,
+    // Bytes leídos/escritos en disco: totales y desde la muestra anterior
+    pub disco_leido: u64,
+    pub disco_escrito: u64,
+    pub disco_leido_intervalo: u64,
+    pub disco_escrito_intervalo: u64,
+}
+
+// Estado del proceso con valores estables para que la interfaz compare sin depender
+// del idioma; se serializan igual que las cadenas que se usaban antes
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Estado {
+    Ejecutando,
+    Dormido,
+    // Sueño no interrumpible (estado D): suele indicar un proceso trabado en disco
+    EsperaIo,
+    Inactivo,
+    Detenido,
+    Zombie,
+    Trazando,
+    // Ya terminó pero sigue listado un instante (X en Linux)
+    Muerto,
+    // Estados de kernels Linux viejos: esperando solo una señal fatal (K),
+    // despertando (W) y estacionado (P)
+    SoloSigkill,
+    Despertando,
+    Estacionado,
+    // Bloqueado esperando un lock (FreeBSD)
+    BloqueadoLock,
+    Desconocido,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Idioma {
+    Es,
+    En,
+}
+
+impl Estado {
+    pub fn desde(status: ProcessStatus) -> Self {
+        match status {
+            ProcessStatus::Run => Estado::Ejecutando,
+            ProcessStatus::Sleep => Estado::Dormido,
+            ProcessStatus::UninterruptibleDiskSleep => Estado::EsperaIo,
+            ProcessStatus::Idle => Estado::Inactivo,
+            ProcessStatus::Stop => Estado::Detenido,
+            ProcessStatus::Zombie => Estado::Zombie,
+            ProcessStatus::Tracing => Estado::Trazando,
+            ProcessStatus::Dead => Estado::Muerto,
+            ProcessStatus::Wakekill => Estado::SoloSigkill,
+            ProcessStatus::Waking => Estado::Despertando,
+            ProcessStatus::Parked => Estado::Estacionado,
+            ProcessStatus::LockBlocked => Estado::BloqueadoLock,
+            _ => Estado::Desconocido,
+        }
+    }
+
+    // Código crudo que sysinfo no supo traducir (en Linux, la letra de /proc como
+    // número), para no perderlo dentro de "desconocido"
+    fn codigo_desconocido(status: ProcessStatus) -> Option<u32> {
+        match status {
+            ProcessStatus::Unknown(codigo) => Some(codigo),
+            _ => None,
+        }
+    }
+
+    fn etiqueta(self, idioma: Idioma) -> &'static str {
+        match (self, idioma) {
+            (Estado::Ejecutando, Idioma::Es) => "Ejecutando",
+            (Estado::Dormido, Idioma::Es) => "Dormido",
+            (Estado::EsperaIo, Idioma::Es) => "Esperando E/S",
+            (Estado::Inactivo, Idioma::Es) => "Inactivo",
+            (Estado::Detenido, Idioma::Es) => "Detenido",
+            (Estado::Zombie, Idioma::Es) => "Zombi",
+            (Estado::Trazando, Idioma::Es) => "Trazando",
+            (Estado::Muerto, Idioma::Es) => "Muerto",
+            (Estado::SoloSigkill, Idioma::Es) => "Esperando (solo SIGKILL)",
+            (Estado::Despertando, Idioma::Es) => "Despertando",
+            (Estado::Estacionado, Idioma::Es) => "Estacionado",
+            (Estado::BloqueadoLock, Idioma::Es) => "Bloqueado en lock",
+            (Estado::Desconocido, Idioma::Es) => "Desconocido",
+            (Estado::Ejecutando, Idioma::En) => "Running",
+            (Estado::Dormido, Idioma::En) => "Sleeping",
+            (Estado::EsperaIo, Idioma::En) => "Waiting for I/O",
+            (Estado::Inactivo, Idioma::En) => "Idle",
+            (Estado::Detenido, Idioma::En) => "Stopped",
+            (Estado::Zombie, Idioma::En) => "Zombie",
+            (Estado::Trazando, Idioma::En) => "Tracing",
+            (Estado::Muerto, Idioma::En) => "Dead",
+            (Estado::SoloSigkill, Idioma::En) => "Wakekill",
+            (Estado::Despertando, Idioma::En) => "Waking",
+            (Estado::Estacionado, Idioma::En) => "Parked",
+            (Estado::BloqueadoLock, Idioma::En) => "Lock blocked",
+            (Estado::Desconocido, Idioma::En) => "Unknown",
+        }
+    }
+}
+
+// ----- Estado en memoria para el muestreo entre invocaciones -----
+#[derive(Clone)]
+pub struct ProcStat {
+    // Identifica la instancia del proceso: si el PID se recicla, cambia
+    pub start_time: u64,
+    // Se conserva para poder informar el nombre cuando el proceso ya terminó
+    pub name: String,
+    pub last_seen: Instant,
+    pub acc_cpu_seconds: f64,
+    pub iteraciones: u32,
+    pub last_cpu_positive: bool,
+    pub ewma_cpu: f64,
+    pub last_disk_read: u64,
+    pub last_disk_written: u64,
+    pub disk_read_delta: u64,
+    pub disk_written_delta: u64,
+    // Últimas HISTORIAL_CPU muestras de CPU, de la más vieja a la más nueva
+    pub historial_cpu: VecDeque<f64>,
+    // Hay un pico de CPU en curso ya informado
+    pub en_pico: bool,
+}
+
+static PROC_STATE: Lazy<Mutex<HashMap<u32, ProcStat>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Los ProcStat no sostienen invariantes críticos: si un hilo entró en pánico con el
+// mutex tomado, a lo sumo se pierde la precisión de una muestra. Por eso se recupera
+// el contenido en lugar de propagar el envenenamiento a todos los comandos.
+pub fn estado_procesos() -> MutexGuard<'static, HashMap<u32, ProcStat>> {
+    PROC_STATE.lock().unwrap_or_else(|e| {
+        eprintln!("PROC_STATE quedó envenenado por un pánico previo; se recupera su contenido");
+        PROC_STATE.clear_poison();
+        e.into_inner()
+    })
+}
+
+// Si ya se tomó una muestra completa. No alcanza con mirar si PROC_STATE está vacío:
+// al arrancar se restauran acumuladores guardados antes de la primera muestra.
+static HUBO_MUESTRA: AtomicBool = AtomicBool::new(false);
+
+// Olvida los acumuladores; la próxima muestra vuelve a ser la primera
+pub fn reiniciar_estado_procesos() {
+    let mut map = estado_procesos();
+    map.clear();
+    HUBO_MUESTRA.store(false, Ordering::Relaxed);
+}
+
+// sysinfo calcula cpu_usage como diferencia entre dos refrescos del mismo System,
+// así que se conserva una única instancia entre invocaciones. new_all() también
+// carga la lista de usuarios, que no se vuelve a refrescar en cada consulta.
+pub static SYSTEM: Lazy<Mutex<System>> = Lazy::new(|| Mutex::new(System::new_all()));
+
+// Histéresis del detector de ráfagas: un proceso empieza a consumir CPU al superar
+// CPU_THRESHOLD y solo deja de hacerlo al bajar de CPU_THRESHOLD_SALIDA, así uno que
+// oscila alrededor de un único umbral no suma iteraciones falsas.
+const CPU_THRESHOLD: f64 = 2.0;
+const CPU_THRESHOLD_SALIDA: f64 = 0.5;
+const EWMA_ALPHA: f64 = 0.25;
+const INTERVALO_ESPERADO: f64 = 1.0;
+// Por debajo de este intervalo (segundos) desde la muestra anterior no se actualizan
+// los acumuladores: sysinfo todavía no recalculó la CPU del proceso
+const ELAPSED_MINIMO: f64 = 0.05;
+// Muestras conservadas por proceso para el minigráfico de CPU
+pub const HISTORIAL_CPU: usize = 60;
+const UMBRAL_PICO_CPU: f64 = 80.0;
+
+// ----- Parámetros del muestreo ajustables en tiempo de ejecución -----
+#[derive(Clone)]
+pub struct Config {
+    pub cpu_threshold: f64,
+    pub cpu_threshold_salida: f64,
+    pub ewma_alpha: f64,
+    // Segundos esperados entre muestras; acota el `elapsed` usado al acumular CPU
+    pub intervalo_esperado: f64,
+    pub elapsed_minimo: f64,
+    pub estrategia_tiempo_total: EstrategiaTiempoTotal,
+    pub al_completar: AlCompletar,
+    pub proteccion: Proteccion,
+    // CPU (%) a partir de la cual se emite "pico_cpu"
+    pub umbral_pico: f64,
+    // Tope de procesos devueltos por obtener_procesos (None = sin límite)
+    pub max_procesos: Option<usize>,
+    // Idioma de las etiquetas de estado
+    pub idioma: Idioma,
+}
+
+// PIDs que no se pueden terminar ni suspender desde la app
+#[derive(Deserialize, Clone)]
+pub struct RangoPid {
+    pub desde: u32,
+    pub hasta: u32,
+}
+
+#[derive(Clone)]
+pub struct Proteccion {
+    pub rangos: Vec<RangoPid>,
+    // Nombres exactos, sin distinguir mayúsculas
+    pub nombres: Vec<String>,
+}
+
+impl Proteccion {
+    // El proceso de la propia app siempre queda protegido, para que no pueda
+    // terminarse a sí misma
+    pub fn protege(&self, pid: u32, nombre: &str) -> bool {
+        pid == std::process::id()
+            || self
+                .rangos
+                .iter()
+                .any(|r| (r.desde..=r.hasta).contains(&pid))
+            || self.nombres.iter().any(|n| n.eq_ignore_ascii_case(nombre))
+    }
+}
+
+// Cómo se estima el tiempo_total (segundos de CPU) sobre el que se mide el avance
+#[derive(Deserialize, Clone, Copy)]
+#[serde(tag = "tipo", content = "valor", rename_all = "lowercase")]
+pub enum EstrategiaTiempoTotal {
+    // CPU 0-100 -> 1-20 s; sin consumo de CPU se recurre a la memoria
+    Cpu,
+    // 10 s + 1 s por cada 50 MB residentes (mínimo 5 s)
+    Memoria,
+    // Mismo valor para todos los procesos, útil para demostraciones reproducibles
+    Fija(f64),
+}
+
+// Qué pasa cuando un proceso acumula su tiempo_total de CPU
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum AlCompletar {
+    // Queda en 100% de avance y 0 de tiempo restante
+    Mantener,
+    // Empieza una iteración nueva: el acumulado vuelve a contar desde el excedente
+    Reciclar,
+}
+
+fn tiempo_total_por_memoria(mem_kb: u64) -> f64 {
+    let mem_mb = (mem_kb as f64) / 1024.0;
+    ((mem_mb / 50.0) + 10.0).round().max(5.0)
+}
+
+fn estimar_tiempo_total(cpu: f64, mem_kb: u64, cfg: &Config) -> f64 {
+    match cfg.estrategia_tiempo_total {
+        EstrategiaTiempoTotal::Cpu if cpu > 0.0 => {
+            let v = ((cpu / 100.0) * 19.0) + 1.0;
+            v.round().clamp(1.0, 20.0)
+        }
+        EstrategiaTiempoTotal::Cpu | EstrategiaTiempoTotal::Memoria => {
+            tiempo_total_por_memoria(mem_kb)
+        }
+        EstrategiaTiempoTotal::Fija(segundos) => segundos,
+    }
+}
+
+pub static CONFIG: Lazy<Mutex<Config>> = Lazy::new(|| {
+    Mutex::new(Config {
+        cpu_threshold: CPU_THRESHOLD,
+        cpu_threshold_salida: CPU_THRESHOLD_SALIDA,
+        ewma_alpha: EWMA_ALPHA,
+        intervalo_esperado: INTERVALO_ESPERADO,
+        elapsed_minimo: ELAPSED_MINIMO,
+        estrategia_tiempo_total: EstrategiaTiempoTotal::Cpu,
+        al_completar: AlCompletar::Mantener,
+        // PID 0 (planificador/idle del kernel) y 1 (init)
+        proteccion: Proteccion {
+            rangos: vec![RangoPid { desde: 0, hasta: 1 }],
+            nombres: Vec::new(),
+        },
+        umbral_pico: UMBRAL_PICO_CPU,
+        max_procesos: None,
+        idioma: Idioma::Es,
+    })
+});
+
+// Por encima de este uso de CPU sostenido (EWMA) el proceso se considera limitado por CPU
+const INTERACTIVIDAD_CPU_MAX: f64 = 50.0;
+
+// Porcentaje (0-100) del tiempo_total ya consumido; sin tiempo total no hay avance
+fn calcular_avance(acc_cpu_seconds: f64, tiempo_total: f64) -> f64 {
+    if tiempo_total.is_nan() || tiempo_total <= 0.0 {
+        return 0.0;
+    }
+    // max() descarta un NaN en el acumulado
+    (acc_cpu_seconds.max(0.0) / tiempo_total * 100.0).min(100.0)
+}
+
+// Puntaje de interactividad 0-10: hasta 7 puntos por bajo consumo sostenido de CPU y
+// hasta 3 por ráfagas cortas repetidas (transiciones a consumir CPU).
+fn calcular_interactividad(ewma_cpu: f64, iteraciones: u32) -> i32 {
+    if ewma_cpu >= INTERACTIVIDAD_CPU_MAX {
+        return 0;
+    }
+    let por_cpu = 7.0 * (1.0 - ewma_cpu / INTERACTIVIDAD_CPU_MAX);
+    let por_rafagas = iteraciones.min(3) as f64;
+    (por_cpu + por_rafagas).round().clamp(0.0, 10.0) as i32
+}
+
+// En Linux los hilos del kernel no tienen línea de comandos y cuelgan de kthreadd
+// (PID 2); algunos sistemas además informan su nombre entre corchetes, como ps.
+fn es_hilo_kernel(process: &sysinfo::Process) -> bool {
+    let nombre = process.name();
+    process.cmd().is_empty()
+        && (process.pid().as_u32() == 2
+            || process.parent().is_some_and(|p| p.as_u32() == 2)
+            || (nombre.starts_with('[') && nombre.ends_with(']')))
+}
+
+// Descripciones para procesos de sistema habituales; las claves van en minúsculas,
+// sin ".exe" y sin el sufijo de los hilos del kernel ("kworker/0:1" -> "kworker")
+static DESCRIPCIONES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
+    HashMap::from([
+        // Linux
+        ("systemd", "Gestor de servicios e init del sistema"),
+        ("init", "Primer proceso del sistema; adopta a los huérfanos"),
+        ("kthreadd", "Crea los hilos del kernel"),
+        ("kworker", "Hilo del kernel que ejecuta tareas diferidas"),
+        (
+            "ksoftirqd",
+            "Hilo del kernel que atiende interrupciones diferidas",
+        ),
+        (
+            "kswapd0",
+            "Hilo del kernel que libera memoria pasando páginas a swap",
+        ),
+        (
+            "migration",
+            "Hilo del kernel que mueve tareas entre núcleos",
+        ),
+        ("rcu_sched", "Hilo del kernel de sincronización RCU"),
+        ("sshd", "Servidor de conexiones SSH"),
+        ("cron", "Ejecuta tareas programadas"),
+        ("dbus-daemon", "Bus de mensajes entre aplicaciones"),
+        ("xorg", "Servidor gráfico X"),
+        ("gnome-shell", "Escritorio GNOME"),
+        ("pulseaudio", "Servidor de sonido"),
+        ("pipewire", "Servidor de audio y video"),
+        ("networkmanager", "Gestiona las conexiones de red"),
+        ("bash", "Intérprete de comandos"),
+        ("zsh", "Intérprete de comandos"),
+        // Windows
+        ("system", "Núcleo de Windows"),
+        ("svchost", "Contenedor de servicios de Windows"),
+        ("csrss", "Subsistema de consola y ventanas de Windows"),
+        ("wininit", "Inicio de los servicios de Windows"),
+        ("winlogon", "Inicio y cierre de sesión de Windows"),
+        ("services", "Administrador de servicios de Windows"),
+        ("lsass", "Autenticación y políticas de seguridad de Windows"),
+        ("smss", "Administrador de sesiones de Windows"),
+        ("explorer", "Explorador de archivos y escritorio de Windows"),
+        ("dwm", "Compositor de ventanas de Windows"),
+        ("taskhostw", "Anfitrión de tareas programadas de Windows"),
+        ("spoolsv", "Cola de impresión"),
+        ("msmpeng", "Antivirus Microsoft Defender"),
+        // macOS
+        ("launchd", "Gestor de servicios e init de macOS"),
+        ("kernel_task", "Núcleo de macOS"),
+        ("windowserver", "Servidor gráfico de macOS"),
+        ("finder", "Explorador de archivos de macOS"),
+    ])
+});
+
+fn describir_proceso(nombre: &str) -> Option<String> {
+    let nombre = nombre.trim_start_matches('[').trim_end_matches(']');
+    let base = nombre.split('/').next().unwrap_or(nombre).to_lowercase();
+    let base = base.strip_suffix(".exe").unwrap_or(&base);
+    DESCRIPCIONES.get(base).map(|d| d.to_string())
+}
+
+// Representación legible de una cantidad de bytes, p. ej. "124.3 MB"
+fn formatear_memoria(bytes: u64) -> String {
+    const UNIDADES: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut valor = bytes as f64;
+    let mut unidad = 0;
+    while valor >= 1024.0 && unidad < UNIDADES.len() - 1 {
+        valor /= 1024.0;
+        unidad += 1;
+    }
+    if unidad == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{valor:.1} {}", UNIDADES[unidad])
+    }
+}
+
+// Parámetros comunes a todos los procesos de una misma muestra
+pub struct Muestra {
+    now: Instant,
+    // Segundos desde la época Unix, para comparar con process.start_time()
+    ahora_epoch: f64,
+    nucleos: f64,
+    normalizar_cpu: bool,
+    cfg: Config,
+    // Copia de la lista de usuarios de sysinfo para resolver el dueño de cada proceso
+    usuarios: Vec<(Uid, String)>,
+}
+
+impl Muestra {
+    pub fn nueva(sys: &System, normalizar_cpu: Option<bool>) -> Result<Self, ErrorSim> {
+        Ok(Muestra {
+            now: Instant::now(),
+            ahora_epoch: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs_f64())
+                .unwrap_or(0.0),
+            // cpu_usage() es relativo a un núcleo: con N núcleos puede llegar a N*100%
+            nucleos: sys.cpus().len().max(1) as f64,
+            normalizar_cpu: normalizar_cpu.unwrap_or(false),
+            cfg: CONFIG.lock()?.clone(),
+            usuarios: sys
+                .users()
+                .iter()
+                .map(|u| (u.id().clone(), u.name().to_string()))
+                .collect(),
+        })
+    }
+
+    fn usuario(&self, uid: &Uid) -> Option<String> {
+        self.usuarios
+            .iter()
+            .find(|(id, _)| id == uid)
+            .map(|(_, nombre)| nombre.clone())
+    }
+}
+
+// Construye el Proceso de una muestra junto con su ProcStat actualizado. No toca
+// PROC_STATE, así que puede ejecutarse en paralelo para varios procesos.
+pub fn muestrear_proceso(
+    pid_u32: u32,
+    process: &sysinfo::Process,
+    muestra: &Muestra,
+    previo: Option<&ProcStat>,
+) -> (Proceso, ProcStat) {
+    let cfg = &muestra.cfg;
+    let now = muestra.now;
+
+    // No todas las versiones de sysinfo distinguen el estado D del sueño normal: en
+    // Linux se confirma con /proc, solo para los que no reporta ya en otro estado
+    let estado = match Estado::desde(process.status()) {
+        Estado::Dormido | Estado::Desconocido if plataforma::en_espera_io(pid_u32) => {
+            Estado::EsperaIo
+        }
+        estado => estado,
+    };
+
+    let cpu_bruto = process.cpu_usage() as f64;
+    let cpu_normalizado = cpu_bruto / muestra.nucleos;
+    // Valor que alimenta las heurísticas (tiempo_total, acumuladores, EWMA)
+    let cpu = if muestra.normalizar_cpu {
+        cpu_normalizado
+    } else {
+        cpu_bruto
+    };
+    // sysinfo reporta la memoria en bytes; todo lo demás se deriva de este valor
+    let memoria_bytes = process.memory();
+    let mem_kb = memoria_bytes / 1024;
+
+    let tiempo_total = estimar_tiempo_total(cpu, mem_kb, cfg);
+
+    let prioridad = plataforma::prioridad(pid_u32).unwrap_or(0);
+
+    // start_time() devuelve 0 en plataformas donde no está disponible
+    let tiempo_inicio = process.start_time();
+    let tiempo_vida = if tiempo_inicio > 0 {
+        (muestra.ahora_epoch - tiempo_inicio as f64).max(0.0)
+    } else {
+        0.0
+    };
+
+    // Un ProcStat con otro start_time pertenece a un proceso anterior que tuvo el
+    // mismo PID: se descarta y se siembra como proceso nuevo.
+    let stat_vigente = previo.filter(|stat| stat.start_time == tiempo_inicio);
+
+    let disco = process.disk_usage();
+    // Segundos de CPU sumados en esta muestra; 0 en la primera de cada proceso
+    let mut delta_cpu_seconds = 0.0;
+    // Falso si la muestra llegó demasiado pronto y se conservan los valores anteriores
+    let mut muestra_nueva = true;
+
+    let mut stat = match stat_vigente {
+        Some(previo) => {
+            let mut stat = previo.clone();
+            let elapsed = now.duration_since(stat.last_seen).as_secs_f64();
+            // Dos consultas casi seguidas (p. ej. un doble disparo de la interfaz):
+            // la CPU aún es la de la muestra anterior, así que no se promedia de nuevo
+            // ni se acumula; last_seen queda igual para medir desde la última válida.
+            muestra_nueva = elapsed >= cfg.elapsed_minimo;
+            if muestra_nueva {
+                // Tras una suspensión del equipo el hueco puede ser enorme y saturaría
+                // el avance de golpe: se acota a dos intervalos de muestreo.
+                let elapsed = elapsed.min(2.0 * cfg.intervalo_esperado);
+                delta_cpu_seconds = (cpu / 100.0) * elapsed;
+                stat.acc_cpu_seconds += delta_cpu_seconds;
+
+                // Actualizar EWMA del uso de CPU
+                stat.ewma_cpu = cfg.ewma_alpha * cpu + (1.0 - cfg.ewma_alpha) * stat.ewma_cpu;
+
+                // Detectar transición a consumir CPU; el umbral depende del estado anterior
+                let now_positive = if stat.last_cpu_positive {
+                    cpu > cfg.cpu_threshold_salida
+                } else {
+                    cpu > cfg.cpu_threshold
+                };
+                if now_positive && !stat.last_cpu_positive {
+                    stat.iteraciones = stat.iteraciones.saturating_add(1);
+                }
+                stat.last_cpu_positive = now_positive;
+                stat.last_seen = now;
+
+                // Un proceso que vive días completa su tiempo_total una y otra vez; al
+                // reciclarlo cada vuelta cuenta como una iteración más
+                if matches!(cfg.al_completar, AlCompletar::Reciclar)
+                    && tiempo_total > 0.0
+                    && stat.acc_cpu_seconds >= tiempo_total
+                {
+                    stat.acc_cpu_seconds %= tiempo_total;
+                    stat.iteraciones = stat.iteraciones.saturating_add(1);
+                }
+
+                // E/S de disco desde la muestra anterior
+                stat.disk_read_delta = disco.total_read_bytes.saturating_sub(stat.last_disk_read);
+                stat.disk_written_delta = disco
+                    .total_written_bytes
+                    .saturating_sub(stat.last_disk_written);
+                stat.last_disk_read = disco.total_read_bytes;
+                stat.last_disk_written = disco.total_written_bytes;
+            }
+            stat
+        }
+        None => {
+            let initial_iter = if cpu > cfg.cpu_threshold { 1 } else { 0 };
+            ProcStat {
+                start_time: tiempo_inicio,
+                name: process.name().to_string(),
+                last_seen: now,
+                acc_cpu_seconds: 0.0,
+                iteraciones: initial_iter,
+                last_cpu_positive: cpu > cfg.cpu_threshold,
+                ewma_cpu: cpu,
+                last_disk_read: disco.total_read_bytes,
+                last_disk_written: disco.total_written_bytes,
+                disk_read_delta: 0,
+                disk_written_delta: 0,
+                historial_cpu: VecDeque::with_capacity(HISTORIAL_CPU),
+                en_pico: false,
+            }
+        }
+    };
+
+    // La memoria queda acotada: al llenarse se descarta la muestra más vieja
+    if muestra_nueva {
+        if stat.historial_cpu.len() == HISTORIAL_CPU {
+            stat.historial_cpu.pop_front();
+        }
+        stat.historial_cpu.push_back(cpu);
+    }
+
+    // Sin permisos exe() llega vacío: se muestra al menos el nombre
+    let ruta = if process.exe().as_os_str().is_empty() {
+        process.name().to_string()
+    } else {
+        process.exe().to_string_lossy().into_owned()
+    };
+
+    let avance = calcular_avance(stat.acc_cpu_seconds, tiempo_total);
+    let tiempo_restante = if tiempo_total > 0.0 {
+        (tiempo_total - stat.acc_cpu_seconds).max(0.0)
+    } else {
+        tiempo_total
+    };
+
+    let proceso = Proceso {
+        pid: pid_u32.to_string(),
+        nombre: process.name().to_string(),
+        descripcion: describir_proceso(process.name()),
+        ruta,
+        cmd: process.cmd().to_vec(),
+        prioridad,
+        tiempo_cpu: cpu_bruto,
+        delta_cpu_seconds,
+        cpu_normalizado,
+        // Un proceso nuevo siembra la EWMA con su CPU actual
+        cpu_suavizado: stat.ewma_cpu,
+        memoria: mem_kb,
+        memoria_bytes,
+        memoria_humana: formatear_memoria(memoria_bytes),
+        memoria_virtual: process.virtual_memory(),
+        swap: plataforma::swap(pid_u32).unwrap_or(0),
+        estado,
+        estado_codigo: Estado::codigo_desconocido(process.status()),
+        estado_etiqueta: estado.etiqueta(cfg.idioma).to_string(),
+        interactividad: calcular_interactividad(stat.ewma_cpu, stat.iteraciones),
+        avance,
+        iteraciones: stat.iteraciones,
+        tiempo_total,
+        tiempo_restante,
+        tiempo_inicio,
+        tiempo_vida,
+        tiempo_ejecucion: process.run_time(),
+        ppid: process.parent().map(|p| p.as_u32()),
+        protegido: cfg.proteccion.protege(pid_u32, process.name()),
+        es_propio: pid_u32 == std::process::id(),
+        // Lo marca tomar_instantanea, que toma FIJADOS una sola vez por muestra
+        fijado: false,
+        es_kernel: es_hilo_kernel(process),
+        // Si la plataforma no informa los hilos se asume un único hilo
+        hilos: plataforma::hilos(pid_u32).unwrap_or(1),
+        usuario: process.user_id().and_then(|uid| muestra.usuario(uid)),
+        afinidad: plataforma::afinidad(pid_u32).unwrap_or_default(),
+        historial_cpu: stat.historial_cpu.iter().copied().collect(),
+        disco_leido: disco.total_read_bytes,
+        disco_escrito: disco.total_written_bytes,
+        disco_leido_intervalo: stat.disk_read_delta,
+        disco_escrito_intervalo: stat.disk_written_delta,
+    };
+    (proceso, stat)
+}
+
+// Procesos que aparecieron o desaparecieron entre dos muestras
+#[derive(Serialize, Clone)]
+pub struct EventoProceso {
+    pub pid: u32,
+    pub nombre: String,
+}
+
+#[derive(Serialize, Clone)]
+pub struct PicoCpu {
+    pub pid: u32,
+    pub nombre: String,
+    // Última muestra por debajo del umbral antes de la subida
+    pub cpu_anterior: f64,
+    pub cpu_actual: f64,
+}
+
+// Un pico se confirma cuando la CPU supera el umbral y la EWMA ya subió al menos a
+// esta fracción de él: una sola muestra alta apenas mueve la EWMA, así que el ruido
+// de una muestra no dispara el evento. El pico termina cuando la EWMA vuelve a bajar
+// de esa marca; recién entonces puede informarse otro.
+const FRACCION_EWMA_PICO: f64 = 0.5;
+
+fn detectar_pico(pid: u32, stat: &mut ProcStat, umbral: f64) -> Option<PicoCpu> {
+    let marca_ewma = umbral * FRACCION_EWMA_PICO;
+    if stat.en_pico {
+        stat.en_pico = stat.ewma_cpu >= marca_ewma;
+        return None;
+    }
+    let cpu = stat.historial_cpu.back().copied().unwrap_or(0.0);
+    if cpu <= umbral || stat.ewma_cpu < marca_ewma {
+        return None;
+    }
+    stat.en_pico = true;
+    let cpu_anterior = stat
+        .historial_cpu
+        .iter()
+        .rev()
+        .skip(1)
+        .find(|&&c| c <= umbral)
+        .copied()
+        .unwrap_or(0.0);
+    Some(PicoCpu {
+        pid,
+        nombre: stat.name.clone(),
+        cpu_anterior,
+        cpu_actual: cpu,
+    })
+}
+
+// Una muestra y lo que pasó desde la anterior; los comandos emiten esto último como
+// eventos de Tauri
+#[derive(Serialize)]
+pub struct Instantanea {
+    pub procesos: Vec<Proceso>,
+    pub iniciados: Vec<EventoProceso>,
+    pub terminados: Vec<EventoProceso>,
+    pub picos: Vec<PicoCpu>,
+    pub vigilados_terminados: Vec<VigiladoTerminado>,
+    // Uso total de CPU (0-100) en el momento de la muestra
+    pub cpu_global: f64,
+}
+
+// ----- Procesos vigilados -----
+// PID -> último nombre conocido. Quien muestree (el hilo de fondo o cualquier
+// comando) detecta la terminación y emite "proceso_vigilado_terminado".
+pub static VIGILADOS: Lazy<Mutex<HashMap<u32, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Serialize, Clone)]
+pub struct VigiladoTerminado {
+    pub pid: u32,
+    pub nombre: String,
+    // Segundos de CPU acumulados hasta la última muestra (0 si nunca se muestreó)
+    pub cpu_acumulada: f64,
+}
+
+// ----- Procesos fijados al principio de la lista -----
+// En el orden en que se fijaron
+pub static FIJADOS: Lazy<Mutex<Vec<u32>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+// Mueve los fijados al principio en orden de fijación; el orden relativo del resto
+// se conserva porque sort_by_key es estable
+fn anteponer_fijados(procesos: &mut [Proceso], fijados: &[u32]) {
+    if fijados.is_empty() {
+        return;
+    }
+    procesos.sort_by_key(|p| {
+        p.pid
+            .parse::<u32>()
+            .ok()
+            .and_then(|pid| fijados.iter().position(|&f| f == pid))
+            .unwrap_or(usize::MAX)
+    });
+}
+
+// Refresca todos los procesos y devuelve la muestra sin ordenar
+pub fn tomar_instantanea(normalizar_cpu: Option<bool>) -> Result<Instantanea, ErrorSim> {
+    let mut sys = SYSTEM.lock()?;
+    sys.refresh_processes();
+    sys.refresh_cpu();
+    let cpu_global = sys.global_cpu_info().cpu_usage() as f64;
+
+    let muestra = Muestra::nueva(&sys, normalizar_cpu)?;
+    let mut iniciados = Vec::new();
+    let mut terminados = Vec::new();
+    let mut picos = Vec::new();
+    // CPU acumulada de los procesos terminados, para informar a los vigilados
+    let mut cpu_final: HashMap<u32, f64> = HashMap::new();
+
+    let mut procesos: Vec<Proceso> = {
+        let mut map = estado_procesos();
+        // Sin muestra previa todo parecería recién iniciado: no se emiten eventos
+        let primera_muestra = !HUBO_MUESTRA.swap(true, Ordering::Relaxed);
+
+        // Orden por PID para que el resultado no dependa del HashMap ni del reparto
+        // entre hilos
+        let mut entradas: Vec<(u32, &sysinfo::Process)> = sys
+            .processes()
+            .iter()
+            .map(|(pid, process)| (pid.as_u32(), process))
+            .collect();
+        entradas.sort_unstable_by_key(|(pid, _)| *pid);
+
+        for &(pid_u32, process) in &entradas {
+            let previo = map
+                .get(&pid_u32)
+                .map(|stat| (stat.start_time == process.start_time(), stat));
+            match previo {
+                Some((true, _)) => {}
+                // PID reciclado: el proceso anterior terminó y empezó otro
+                Some((false, stat)) => {
+                    cpu_final.insert(pid_u32, stat.acc_cpu_seconds);
+                    terminados.push(EventoProceso {
+                        pid: pid_u32,
+                        nombre: stat.name.clone(),
+                    });
+                    iniciados.push(EventoProceso {
+                        pid: pid_u32,
+                        nombre: process.name().to_string(),
+                    });
+                }
+                None if !primera_muestra => iniciados.push(EventoProceso {
+                    pid: pid_u32,
+                    nombre: process.name().to_string(),
+                }),
+                None => {}
+            }
+        }
+
+        // La construcción de cada Proceso solo lee el mapa y se reparte entre hilos;
+        // collect() conserva el orden de las entradas.
+        let muestras: Vec<(u32, Proceso, ProcStat)> = entradas
+            .par_iter()
+            .map(|&(pid_u32, process)| {
+                let (proceso, stat) =
+                    muestrear_proceso(pid_u32, process, &muestra, map.get(&pid_u32));
+                (pid_u32, proceso, stat)
+            })
+            .collect();
+
+        // Las escrituras al mapa quedan en una pasada secuencial corta. En la primera
+        // muestra los picos solo se marcan, igual que los demás eventos.
+        muestras
+            .into_iter()
+            .map(|(pid_u32, proceso, mut stat)| {
+                if let Some(pico) = detectar_pico(pid_u32, &mut stat, muestra.cfg.umbral_pico) {
+                    if !primera_muestra {
+                        picos.push(pico);
+                    }
+                }
+                map.insert(pid_u32, stat);
+                proceso
+            })
+            .collect()
+    };
+
+    // Limpieza de procesos eliminados
+    {
+        let current_pids: HashSet<u32> = sys.processes().keys().map(|pid| pid.as_u32()).collect();
+        let mut map = estado_procesos();
+        let stale: Vec<u32> = map
+            .keys()
+            .cloned()
+            .filter(|k| !current_pids.contains(k))
+            .collect();
+        for k in stale {
+            // El nombre se toma del ProcStat antes de descartarlo
+            if let Some(stat) = map.remove(&k) {
+                cpu_final.insert(k, stat.acc_cpu_seconds);
+                terminados.push(EventoProceso {
+                    pid: k,
+                    nombre: stat.name,
+                });
+            }
+        }
+    }
+
+    // Los fijados que terminaron (o cuyo PID se recicló) se quitan; el resto se marca
+    {
+        let mut fijados = FIJADOS.lock()?;
+        let reciclados: HashSet<u32> = terminados.iter().map(|e| e.pid).collect();
+        fijados
+            .retain(|&pid| !reciclados.contains(&pid) && sys.process(Pid::from_u32(pid)).is_some());
+        for p in &mut procesos {
+            p.fijado = p.pid.parse().is_ok_and(|pid: u32| fijados.contains(&pid));
+        }
+    }
+
+    procesos.extend(avanzar_simulados(&muestra)?);
+
+    // Un vigilado terminó si su PID ya no existe o fue reciclado por otro proceso
+    let vigilados_terminados = {
+        let mut vigilados = VIGILADOS.lock()?;
+        let reciclados: HashSet<u32> = terminados.iter().map(|e| e.pid).collect();
+        let caidos: Vec<u32> = vigilados
+            .keys()
+            .copied()
+            .filter(|&pid| reciclados.contains(&pid) || sys.process(Pid::from_u32(pid)).is_none())
+            .collect();
+        caidos
+            .into_iter()
+            .filter_map(|pid| {
+                let nombre = vigilados.remove(&pid)?;
+                Some(VigiladoTerminado {
+                    pid,
+                    nombre,
+                    cpu_acumulada: cpu_final.get(&pid).copied().unwrap_or(0.0),
+                })
+            })
+            .collect()
+    };
+
+    // obtener_deltas compara contra la muestra anterior
+    {
+        let mut ultimas = ULTIMAS_MUESTRAS.lock()?;
+        ultimas.anterior = std::mem::replace(&mut ultimas.actual, procesos.clone());
+    }
+
+    Ok(Instantanea {
+        procesos,
+        iniciados,
+        terminados,
+        picos,
+        vigilados_terminados,
+        cpu_global,
+    })
+}
+
+// Muestra completa con los procesos de mayor a menor CPU
+pub fn snapshot(normalizar_cpu: Option<bool>) -> Result<Instantanea, ErrorSim> {
+    let mut instantanea = tomar_instantanea(normalizar_cpu)?;
+    ordenar_procesos(&mut instantanea.procesos, CriterioOrden::Cpu, true);
+    Ok(instantanea)
+}
+
+// Filtra, ordena y pagina una muestra ya tomada
+pub fn consultar_procesos(
+    procesos: Vec<Proceso>,
+    filtro: &Filtro,
+    orden: CriterioOrden,
+    descendente: bool,
+    offset: usize,
+    limite: Option<usize>,
+) -> Result<PaginaProcesos, ErrorSim> {
+    let aviso_permisos = !procesos.iter().any(|p| {
+        !p.es_propio
+            && p.pid
+                .parse::<u32>()
+                .is_ok_and(|pid| pid < PID_SIMULADO_BASE)
+    });
+    let mut out = procesos;
+    out.retain(|p| filtro.cumple(p));
+    let aviso = if aviso_permisos {
+        Some(AvisoProcesos::SinPermisos)
+    } else if out.is_empty() {
+        Some(AvisoProcesos::SinCoincidencias)
+    } else {
+        None
+    };
+    let fijados = FIJADOS.lock()?.clone();
+    // El tope recorta la lista devuelta; PROC_STATE ya se actualizó y limpió completo
+    if let Some(max) = CONFIG.lock()?.max_procesos {
+        out = aplicar_tope(out, max, &fijados);
+    }
+    ordenar_procesos(&mut out, orden, descendente);
+    anteponer_fijados(&mut out, &fijados);
+    // La página se corta al final para que sea estable respecto del orden y el filtro
+    let mut pagina = paginar(out, offset, limite);
+    pagina.aviso = aviso;
+    Ok(pagina)
+}
+
+// Los fijados ocupan primero los lugares del tope, en orden de fijación, para que no
+// desaparezcan al bajar su CPU; los que quedan se llenan con los de mayor CPU. Si hay
+// más fijados que lugares, sobran los últimos fijados.
+fn aplicar_tope(procesos: Vec<Proceso>, max: usize, fijados: &[u32]) -> Vec<Proceso> {
+    let (mut elegidos, resto): (Vec<Proceso>, Vec<Proceso>) =
+        procesos.into_iter().partition(|p| p.fijado);
+    anteponer_fijados(&mut elegidos, fijados);
+    elegidos.truncate(max);
+    let libres = max - elegidos.len();
+    elegidos.extend(mayores_por_cpu(resto, libres));
+    elegidos
+}
+
+// Selección parcial en O(len) y orden solo de los `n` elegidos, en vez de ordenar la
+// lista completa para después cortarla
+pub fn mayores_por_cpu(mut procesos: Vec<Proceso>, n: usize) -> Vec<Proceso> {
+    let pid = |p: &Proceso| p.pid.parse::<u32>().unwrap_or(0);
+    // Mismo desempate por PID que ordenar_procesos
+    let por_cpu = |a: &Proceso, b: &Proceso| {
+        b.tiempo_cpu
+            .total_cmp(&a.tiempo_cpu)
+            .then_with(|| pid(a).cmp(&pid(b)))
+    };
+    if n == 0 {
+        return Vec::new();
+    }
+    if procesos.len() > n {
+        procesos.select_nth_unstable_by(n - 1, por_cpu);
+        procesos.truncate(n);
+    }
+    procesos.sort_by(por_cpu);
+    procesos
+}
+
+// ----- Paginación -----
+#[derive(Serialize, Deserialize)]
+pub struct PaginaProcesos {
+    // Cantidad total tras filtrar, antes de cortar la página
+    pub total: usize,
+    pub items: Vec<Proceso>,
+    // Explica una lista vacía o sospechosamente corta; no es un error
+    pub aviso: Option<AvisoProcesos>,
+    // Token para pedir a obtener_procesos solo lo que cambió desde esta respuesta
+    pub version: u64,
+    // Falso si `items` trae solo los procesos cambiados desde la versión pedida
+    pub completa: bool,
+    // PIDs que la interfaz debe quitar (solo en respuestas incrementales)
+    pub eliminados: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum AvisoProcesos {
+    // Solo se ve el proceso propio: sandbox, hidepid en Linux o falta de permisos en
+    // macOS. Un sistema real siempre tiene otros procesos.
+    SinPermisos,
+    // Hay procesos visibles, pero ninguno pasa el filtro
+    SinCoincidencias,
+}
+
+fn paginar(procesos: Vec<Proceso>, offset: usize, limite: Option<usize>) -> PaginaProcesos {
+    let total = procesos.len();
+    let items = procesos
+        .into_iter()
+        .skip(offset)
+        .take(limite.unwrap_or(usize::MAX))
+        .collect();
+    PaginaProcesos {
+        total,
+        items,
+        aviso: None,
+        version: 0,
+        completa: true,
+        eliminados: Vec::new(),
+    }
+}
+
+// ----- Filtrado y ordenamiento de la lista -----
+#[derive(Deserialize, Default)]
+pub struct Filtro {
+    // Subcadena del nombre, sin distinguir mayúsculas
+    pub nombre: Option<String>,
+    // Estado exacto, p. ej. "ejecutando"
+    pub estado: Option<Estado>,
+    // Nombre exacto del usuario dueño
+    pub usuario: Option<String>,
+    // Excluye el proceso de la propia app, que infla su CPU al refrescar
+    pub ocultar_propio: Option<bool>,
+    // Zombis e hilos del kernel no aportan CPU ni memoria; se ocultan salvo que se pidan
+    pub incluir_zombies: Option<bool>,
+    pub incluir_kernel: Option<bool>,
+}
+
+impl Filtro {
+    fn cumple(&self, p: &Proceso) -> bool {
+        let por_nombre = self
+            .nombre
+            .as_ref()
+            .is_none_or(|n| p.nombre.to_lowercase().contains(&n.to_lowercase()));
+        let por_estado = self.estado.as_ref().is_none_or(|&e| p.estado == e);
+        let por_usuario = self
+            .usuario
+            .as_ref()
+            .is_none_or(|u| p.usuario.as_ref() == Some(u));
+        let por_propio = !(self.ocultar_propio.unwrap_or(false) && p.es_propio);
+        let por_zombie = self.incluir_zombies.unwrap_or(false) || p.estado != Estado::Zombie;
+        let por_kernel = self.incluir_kernel.unwrap_or(false) || !p.es_kernel;
+        por_nombre && por_estado && por_usuario && por_propio && por_zombie && por_kernel
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CriterioOrden {
+    #[default]
+    Cpu,
+    Memoria,
+    Nombre,
+    Pid,
+    Vida,
+}
+
+fn ordenar_procesos(procesos: &mut [Proceso], orden: CriterioOrden, descendente: bool) {
+    use std::cmp::Ordering as Orden;
+
+    let pid = |p: &Proceso| p.pid.parse::<u32>().unwrap_or(0);
+    procesos.sort_by(|a, b| {
+        let ascendente = match orden {
+            CriterioOrden::Cpu => a
+                .tiempo_cpu
+                .partial_cmp(&b.tiempo_cpu)
+                .unwrap_or(Orden::Equal),
+            CriterioOrden::Memoria => a.memoria_bytes.cmp(&b.memoria_bytes),
+            CriterioOrden::Nombre => a.nombre.to_lowercase().cmp(&b.nombre.to_lowercase()),
+            CriterioOrden::Pid => pid(a).cmp(&pid(b)),
+            CriterioOrden::Vida => a
+                .tiempo_vida
+                .partial_cmp(&b.tiempo_vida)
+                .unwrap_or(Orden::Equal),
+        };
+        let orden = if descendente {
+            ascendente.reverse()
+        } else {
+            ascendente
+        };
+        // sysinfo entrega los procesos en el orden de un HashMap: sin este desempate
+        // las filas con igual clave (p. ej. muchas con 0% de CPU) cambiarían de lugar
+        // entre refrescos. El PID va siempre ascendente.
+        orden.then_with(|| pid(a).cmp(&pid(b)))
+    });
+}
+
+// ----- Procesos simulados -----
+// Filas sintéticas que se suman a cada muestra para demostraciones sin procesos
+// reales interesantes. Consumen una CPU constante, así que su avance es predecible.
+// Sus PIDs salen de un rango reservado muy por encima de los del sistema.
+const PID_SIMULADO_BASE: u32 = 3_000_000_000;
+
+pub struct ProcesoSimulado {
+    pub pid: u32,
+    pub nombre: String,
+    // Porcentaje de un núcleo, como process.cpu_usage()
+    pub cpu: f64,
+    pub memoria_bytes: u64,
+    pub tiempo_total: f64,
+    pub acc_cpu_seconds: f64,
+    pub creado: Instant,
+    pub creado_epoch: u64,
+    pub last_seen: Instant,
+    pub historial_cpu: VecDeque<f64>,
+}
+
+pub static SIMULADOS: Lazy<Mutex<Vec<ProcesoSimulado>>> = Lazy::new(|| Mutex::new(Vec::new()));
+pub static SIGUIENTE_PID_SIMULADO: AtomicU32 = AtomicU32::new(PID_SIMULADO_BASE);
+
+// Avanza cada proceso simulado con las mismas reglas de intervalo que los reales
+fn avanzar_simulados(muestra: &Muestra) -> Result<Vec<Proceso>, ErrorSim> {
+    let cfg = &muestra.cfg;
+    let mut simulados = SIMULADOS.lock()?;
+    Ok(simulados
+        .iter_mut()
+        .map(|s| {
+            let cpu_normalizado = s.cpu / muestra.nucleos;
+            let cpu = if muestra.normalizar_cpu {
+                cpu_normalizado
+            } else {
+                s.cpu
+            };
+            let elapsed = muestra.now.duration_since(s.last_seen).as_secs_f64();
+            let mut delta_cpu_seconds = 0.0;
+            if elapsed >= cfg.elapsed_minimo {
+                delta_cpu_seconds = (cpu / 100.0) * elapsed.min(2.0 * cfg.intervalo_esperado);
+                s.acc_cpu_seconds += delta_cpu_seconds;
+                s.last_seen = muestra.now;
+                if s.historial_cpu.len() == HISTORIAL_CPU {
+                    s.historial_cpu.pop_front();
+                }
+                s.historial_cpu.push_back(cpu);
+            }
+
+            Proceso {
+                pid: s.pid.to_string(),
+                nombre: s.nombre.clone(),
+                descripcion: None,
+                ruta: s.nombre.clone(),
+                cmd: Vec::new(),
+                prioridad: 0,
+                tiempo_cpu: s.cpu,
+                delta_cpu_seconds,
+                cpu_normalizado,
+                cpu_suavizado: cpu,
+                memoria: s.memoria_bytes / 1024,
+                memoria_bytes: s.memoria_bytes,
+                memoria_humana: formatear_memoria(s.memoria_bytes),
+                memoria_virtual: s.memoria_bytes,
+                swap: 0,
+                estado: Estado::Ejecutando,
+                estado_codigo: None,
+                estado_etiqueta: Estado::Ejecutando.etiqueta(cfg.idioma).to_string(),
+                interactividad: calcular_interactividad(cpu, 0),
+                avance: calcular_avance(s.acc_cpu_seconds, s.tiempo_total),
+                iteraciones: 0,
+                tiempo_total: s.tiempo_total,
+                tiempo_restante: (s.tiempo_total - s.acc_cpu_seconds).max(0.0),
+                tiempo_inicio: s.creado_epoch,
+                tiempo_vida: muestra.now.duration_since(s.creado).as_secs_f64(),
+                tiempo_ejecucion: muestra.now.duration_since(s.creado).as_secs(),
+                ppid: None,
+                // No existen en el sistema: no hay nada que terminar ni suspender
+                protegido: true,
+                es_propio: false,
+                fijado: false,
+                es_kernel: false,
+                hilos: 1,
+                usuario: None,
+                afinidad: Vec::new(),
+                historial_cpu: s.historial_cpu.iter().copied().collect(),
+                disco_leido: 0,
+                disco_escrito: 0,
+                disco_leido_intervalo: 0,
+                disco_escrito_intervalo: 0,
+            }
+        })
+        .collect())
+}
+
+// ----- Variación entre las dos últimas muestras -----
+// La anterior y la actual, sean del muestreo en segundo plano o de una consulta
+#[derive(Default)]
+pub struct UltimasMuestras {
+    pub anterior: Vec<Proceso>,
+    pub actual: Vec<Proceso>,
+}
+
+pub static ULTIMAS_MUESTRAS: Lazy<Mutex<UltimasMuestras>> =
+    Lazy::new(|| Mutex::new(UltimasMuestras::default()));
+
+// ----- Árbol de procesos -----
+#[derive(Serialize)]
+pub struct NodoProceso {
+    pub proceso: Proceso,
+    pub hijos: Vec<NodoProceso>,
+}
+
+// Raíces = procesos cuyo padre no está en la muestra. Cada proceso se visita una sola
+// vez, así que un ciclo en los enlaces padre-hijo no puede causar un bucle infinito;
+// los procesos atrapados en un ciclo (sin raíz alcanzable) se agregan como raíces.
+pub fn construir_arbol(procesos: Vec<Proceso>) -> Vec<NodoProceso> {
+    let pids: Vec<u32> = procesos
+        .iter()
+        .map(|p| p.pid.parse().unwrap_or(0))
+        .collect();
+    let indice: HashMap<u32, usize> = pids.iter().enumerate().map(|(i, &p)| (p, i)).collect();
+
+    let mut hijos: Vec<Vec<usize>> = vec![Vec::new(); procesos.len()];
+    let mut raices = Vec::new();
+    for (i, p) in procesos.iter().enumerate() {
+        match p.ppid.and_then(|pp| indice.get(&pp)) {
+            Some(&padre) if padre != i => hijos[padre].push(i),
+            _ => raices.push(i),
+        }
+    }
+
+    let mut pendientes: Vec<Option<Proceso>> = procesos.into_iter().map(Some).collect();
+    let mut visitado = vec![false; pendientes.len()];
+
+    fn construir(
+        i: usize,
+        hijos: &[Vec<usize>],
+        pendientes: &mut [Option<Proceso>],
+        visitado: &mut [bool],
+    ) -> Option<NodoProceso> {
+        if visitado[i] {
+            return None;
+        }
+        visitado[i] = true;
+        let proceso = pendientes[i].take()?;
+        let hijos_nodo = hijos[i]
+            .iter()
+            .filter_map(|&h| construir(h, hijos, pendientes, visitado))
+            .collect();
+        Some(NodoProceso {
+            proceso,
+            hijos: hijos_nodo,
+        })
+    }
+
+    let mut arbol: Vec<NodoProceso> = raices
+        .into_iter()
+        .filter_map(|r| construir(r, &hijos, &mut pendientes, &mut visitado))
+        .collect();
+
+    for i in 0..visitado.len() {
+        if let Some(nodo) = construir(i, &hijos, &mut pendientes, &mut visitado) {
+            arbol.push(nodo);
+        }
+    }
+
+    arbol
+}
+
+// ----- Depuración de las heurísticas (solo en builds de desarrollo) -----
+#[cfg(debug_assertions)]
+#[derive(Serialize)]
+pub struct EstadoInterno {
+    pub pid: u32,
+    pub nombre: String,
+    pub acc_cpu_seconds: f64,
+    pub ewma_cpu: f64,
+    pub iteraciones: u32,
+    // Segundos desde la última muestra nueva de este PID
+    pub edad_last_seen: f64,
+}
+
+#[cfg(debug_assertions)]
+pub fn estado_interno() -> Vec<EstadoInterno> {
+    let ahora = Instant::now();
+    let mut estado: Vec<EstadoInterno> = estado_procesos()
+        .iter()
+        .map(|(&pid, stat)| EstadoInterno {
+            pid,
+            nombre: stat.name.clone(),
+            acc_cpu_seconds: stat.acc_cpu_seconds,
+            ewma_cpu: stat.ewma_cpu,
+            iteraciones: stat.iteraciones,
+            edad_last_seen: ahora.duration_since(stat.last_seen).as_secs_f64(),
+        })
+        .collect();
+    estado.sort_by_key(|e| e.pid);
+    estado
+}
+
+// ----- Respuestas incrementales -----
+// Lo que se compara para decidir si un proceso cambió: CPU (a una décima), memoria,
+// estado e inicio (un PID reciclado es otro proceso)
+type Huella = (i64, u64, Estado, u64);
+
+fn huella(p: &Proceso) -> Huella {
+    (
+        (p.tiempo_cpu * 10.0).round() as i64,
+        p.memoria_bytes,
+        p.estado,
+        p.tiempo_inicio,
+    )
+}
+
+// Bajas recordadas; una versión más vieja que la primera conservada recibe la lista
+// completa
+const MAX_ELIMINADOS: usize = 10_000;
+
+#[derive(Default)]
+struct Versiones {
+    actual: u64,
+    // Por PID: última huella y versión en la que cambió
+    huellas: HashMap<String, (Huella, u64)>,
+    // PIDs desaparecidos con la versión en la que se notó, del más viejo al más nuevo
+    eliminados: VecDeque<(u64, String)>,
+    // Versión más vieja para la que `eliminados` está completo
+    horizonte: u64,
+}
+
+impl Versiones {
+    // Incorpora la lista visible (filtrada y con tope) y devuelve la versión nueva
+    fn registrar(&mut self, procesos: &[Proceso]) -> u64 {
+        self.actual += 1;
+        let mut vivos = HashSet::with_capacity(procesos.len());
+        for p in procesos {
+            vivos.insert(p.pid.as_str());
+            let h = huella(p);
+            if self
+                .huellas
+                .get(&p.pid)
+                .is_none_or(|&(previa, _)| previa != h)
+            {
+                self.huellas.insert(p.pid.clone(), (h, self.actual));
+            }
+        }
+        let muertos: Vec<String> = self
+            .huellas
+            .keys()
+            .filter(|pid| !vivos.contains(pid.as_str()))
+            .cloned()
+            .collect();
+        for pid in muertos {
+            self.huellas.remove(&pid);
+            self.eliminados.push_back((self.actual, pid));
+        }
+        while self.eliminados.len() > MAX_ELIMINADOS {
+            if let Some((v, _)) = self.eliminados.pop_front() {
+                self.horizonte = v;
+            }
+        }
+        self.actual
+    }
+
+    // PIDs cambiados y eliminados después de `version`; None si no se puede saber
+    // (versión futura, de otra ejecución o anterior al horizonte)
+    fn desde(&self, version: u64) -> Option<(HashSet<String>, Vec<String>)> {
+        if version > self.actual || version < self.horizonte {
+            return None;
+        }
+        let cambiados = self
+            .huellas
+            .iter()
+            .filter(|(_, &(_, v))| v > version)
+            .map(|(pid, _)| pid.clone())
+            .collect();
+        let eliminados = self
+            .eliminados
+            .iter()
+            .filter(|(v, _)| *v > version)
+            .map(|(_, pid)| pid.clone())
+            .collect();
+        Some((cambiados, eliminados))
+    }
+}
+
+static VERSIONES: Lazy<Mutex<Versiones>> = Lazy::new(|| Mutex::new(Versiones::default()));
+
+// Con `version` (el token de una respuesta anterior) solo se devuelven los procesos
+// que cambiaron desde entonces, más los PIDs a quitar. La vista incremental es la de
+// la lista filtrada completa, así que `version` no admite `offset` ni `limite`. Sin
+// `version`, la página entera.
+pub fn consultar_procesos_version(
+    procesos: Vec<Proceso>,
+    filtro: &Filtro,
+    orden: CriterioOrden,
+    descendente: bool,
+    offset: Option<usize>,
+    limite: Option<usize>,
+    version: Option<u64>,
+) -> Result<PaginaProcesos, ErrorSim> {
+    if version.is_some() && (offset.is_some() || limite.is_some()) {
+        return Err(ErrorSim::EntradaInvalida(
+            "El modo incremental (version) no admite offset ni limite".to_string(),
+        ));
+    }
+    // El filtro se aplica después de actualizar los ProcStat, para que los
+    // acumuladores de los procesos ocultos sigan al día. Sus ProcStat se limpian igual
+    // al terminar, porque la limpieza mira la lista completa del sistema y no la
+    // filtrada.
+    let vista = consultar_procesos(procesos, filtro, orden, descendente, 0, None)?;
+    // Las huellas son las de la vista: un proceso que empieza a pasar el filtro (o
+    // entra bajo el tope) cuenta como cambiado y uno que deja de pasarlo, como
+    // eliminado
+    let (nueva_version, cambios) = {
+        let mut versiones = VERSIONES.lock()?;
+        let nueva = versiones.registrar(&vista.items);
+        (nueva, version.and_then(|v| versiones.desde(v)))
+    };
+    let mut pagina = match cambios {
+        Some((cambiados, mut eliminados)) => {
+            // Un PID que se fue y volvió después de `version` llega como cambiado
+            let visibles: HashSet<&str> = vista.items.iter().map(|p| p.pid.as_str()).collect();
+            eliminados.retain(|pid| !visibles.contains(pid.as_str()));
+            eliminados.sort_by_key(|pid| pid.parse::<u32>().unwrap_or(u32::MAX));
+            let mut pagina = paginar(vista.items, 0, None);
+            pagina.items.retain(|p| cambiados.contains(&p.pid));
+            pagina.completa = false;
+            pagina.eliminados = eliminados;
+            pagina
+        }
+        None => paginar(vista.items, offset.unwrap_or(0), limite),
+    };
+    pagina.aviso = vista.aviso;
+    pagina.version = nueva_version;
+    Ok(pagina)
+}
+
+// ----- Intervalo del muestreo en segundo plano -----
+// Por debajo de este uso global de CPU (%) una muestra sin cambios cuenta como ociosa
+const CPU_OCIOSA: f64 = 10.0;
+// Muestras ociosas seguidas antes de duplicar el intervalo
+const MUESTRAS_OCIOSAS: u32 = 3;
+
+// Intervalo del muestreo en segundo plano: se duplica mientras el sistema siga ocioso,
+// hasta `maximo`, y vuelve a `minimo` en cuanto hay actividad
+pub struct IntervaloAdaptativo {
+    pub minimo: Duration,
+    maximo: Duration,
+    actual: Duration,
+    ociosas: u32,
+}
+
+impl IntervaloAdaptativo {
+    pub fn nuevo(minimo: Duration, maximo: Duration) -> Self {
+        IntervaloAdaptativo {
+            minimo,
+            maximo,
+            actual: minimo,
+            ociosas: 0,
+        }
+    }
+
+    pub fn registrar(&mut self, ociosa: bool) -> Duration {
+        if !ociosa {
+            self.ociosas = 0;
+            self.actual = self.minimo;
+        } else {
+            self.ociosas += 1;
+            if self.ociosas >= MUESTRAS_OCIOSAS {
+                self.ociosas = 0;
+                self.actual = (self.actual * 2).min(self.maximo);
+            }
+        }
+        self.actual
+    }
+}
+
+// Ociosa: CPU global baja, sin procesos nuevos ni terminados y sin cambios de estado
+// respecto de la muestra anterior (que se actualiza en `estados`)
+pub fn muestra_ociosa(instantanea: &Instantanea, estados: &mut HashMap<u32, Estado>) -> bool {
+    let mut sin_cambios = instantanea.iniciados.is_empty() && instantanea.terminados.is_empty();
+    let mut nuevos = HashMap::with_capacity(instantanea.procesos.len());
+    for p in &instantanea.procesos {
+        let pid = p.pid.parse().unwrap_or(0);
+        sin_cambios &= estados.get(&pid) == Some(&p.estado);
+        nuevos.insert(pid, p.estado);
+    }
+    *estados = nuevos;
+    sin_cambios && instantanea.cpu_global < CPU_OCIOSA
+}
+
+// ----- Capturas con nombre para comparar más tarde -----
+struct Captura {
+    procesos: Vec<Proceso>,
+}
+
+static CAPTURAS: Lazy<Mutex<HashMap<String, Captura>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Serialize)]
+pub struct CambioProceso {
+    pub pid: String,
+    pub nombre: String,
+    pub cpu_antes: f64,
+    pub cpu_despues: f64,
+    pub memoria_antes: u64,
+    pub memoria_despues: u64,
+}
+
+#[derive(Serialize)]
+pub struct DiffInstantanea {
+    pub nuevos: Vec<Proceso>,
+    pub desaparecidos: Vec<Proceso>,
+    // Procesos presentes en ambas con CPU o memoria distintas, de mayor a menor
+    // variación de CPU
+    pub cambiados: Vec<CambioProceso>,
+}
+
+// Guarda `procesos` con `nombre`, reemplazando una captura anterior homónima;
+// devuelve la cantidad de procesos capturados
+pub fn guardar_captura(nombre: String, procesos: Vec<Proceso>) -> Result<usize, ErrorSim> {
+    let cantidad = procesos.len();
+    CAPTURAS.lock()?.insert(nombre, Captura { procesos });
+    Ok(cantidad)
+}
+
+pub fn procesos_capturados(nombre: &str) -> Result<Vec<Proceso>, ErrorSim> {
+    CAPTURAS
+        .lock()?
+        .get(nombre)
+        .map(|c| c.procesos.clone())
+        .ok_or_else(|| ErrorSim::EntradaInvalida(format!("No existe la captura {nombre}")))
+}
+
+// Un proceso es el mismo en ambas muestras si coinciden el PID y el instante de
+// inicio; un PID reciclado cuenta como desaparecido y nuevo.
+pub fn diferenciar(anteriores: Vec<Proceso>, actuales: Vec<Proceso>) -> DiffInstantanea {
+    let clave = |p: &Proceso| (p.pid.clone(), p.tiempo_inicio);
+    let mut previos: HashMap<(String, u64), Proceso> =
+        anteriores.into_iter().map(|p| (clave(&p), p)).collect();
+
+    let mut nuevos = Vec::new();
+    let mut cambiados = Vec::new();
+    for p in actuales {
+        match previos.remove(&clave(&p)) {
+            None => nuevos.push(p),
+            Some(antes) => {
+                if antes.tiempo_cpu != p.tiempo_cpu || antes.memoria_bytes != p.memoria_bytes {
+                    cambiados.push(CambioProceso {
+                        pid: p.pid,
+                        nombre: p.nombre,
+                        cpu_antes: antes.tiempo_cpu,
+                        cpu_despues: p.tiempo_cpu,
+                        memoria_antes: antes.memoria_bytes,
+                        memoria_despues: p.memoria_bytes,
+                    });
+                }
+            }
+        }
+    }
+    cambiados.sort_by(|a, b| {
+        let variacion = |c: &CambioProceso| (c.cpu_despues - c.cpu_antes).abs();
+        variacion(b).total_cmp(&variacion(a))
+    });
+
+    let mut desaparecidos: Vec<Proceso> = previos.into_values().collect();
+    desaparecidos.sort_by_key(|p| p.pid.parse::<u32>().unwrap_or(0));
+
+    DiffInstantanea {
+        nuevos,
+        desaparecidos,
+        cambiados,
+    }
+}
+
+// ----- Variación entre las dos últimas muestras -----
+#[derive(Serialize)]
+pub struct DeltaProceso {
+    pub pid: String,
+    pub nombre: String,
+    // Puntos de CPU y bytes residentes respecto de la muestra anterior
+    pub delta_cpu: f64,
+    pub delta_memoria: i64,
+    // No estaba en la muestra anterior (o su PID se recicló); los deltas son 0
+    pub nuevo: bool,
+}
+
+// Compara las dos últimas muestras ya tomadas
+pub fn deltas_ultimas_muestras() -> Result<Vec<DeltaProceso>, ErrorSim> {
+    let ultimas = ULTIMAS_MUESTRAS.lock()?;
+    let previos: HashMap<(&str, u64), &Proceso> = ultimas
+        .anterior
+        .iter()
+        .map(|p| ((p.pid.as_str(), p.tiempo_inicio), p))
+        .collect();
+    Ok(ultimas
+        .actual
+        .iter()
+        .map(|p| {
+            let antes = previos.get(&(p.pid.as_str(), p.tiempo_inicio));
+            DeltaProceso {
+                pid: p.pid.clone(),
+                nombre: p.nombre.clone(),
+                delta_cpu: antes.map_or(0.0, |a| p.tiempo_cpu - a.tiempo_cpu),
+                delta_memoria: antes.map_or(0, |a| p.memoria_bytes as i64 - a.memoria_bytes as i64),
+                nuevo: antes.is_none(),
+            }
+        })
+        .collect())
+}
+
+// ----- Agrupación por aplicación -----
+// Procesos con el mismo nombre (p. ej. los renderizadores de un navegador) se suman
+// en una sola fila
+#[derive(Serialize)]
+pub struct GrupoProceso {
+    pub nombre: String,
+    pub cpu: f64,
+    pub memoria_bytes: u64,
+    pub hilos: u32,
+    pub pids: Vec<u32>,
+}
+
+// Ordenados como la lista de procesos: CPU total descendente
+pub fn agrupar_por_nombre(procesos: &[Proceso]) -> Vec<GrupoProceso> {
+    let mut grupos: HashMap<&str, GrupoProceso> = HashMap::new();
+    for p in procesos {
+        let grupo = grupos.entry(&p.nombre).or_insert_with(|| GrupoProceso {
+            nombre: p.nombre.clone(),
+            cpu: 0.0,
+            memoria_bytes: 0,
+            hilos: 0,
+            pids: Vec::new(),
+        });
+        grupo.cpu += p.tiempo_cpu;
+        grupo.memoria_bytes = grupo.memoria_bytes.saturating_add(p.memoria_bytes);
+        grupo.hilos = grupo.hilos.saturating_add(p.hilos);
+        grupo.pids.push(p.pid.parse().unwrap_or(0));
+    }
+
+    let mut grupos: Vec<GrupoProceso> = grupos.into_values().collect();
+    for g in &mut grupos {
+        g.pids.sort_unstable();
+    }
+    // A igual CPU, por nombre, para que el orden no dependa del HashMap
+    grupos.sort_by(|a, b| {
+        b.cpu
+            .total_cmp(&a.cpu)
+            .then_with(|| a.nombre.cmp(&b.nombre))
+    });
+    grupos
+}
+
+// ----- Entradas del simulador a partir de procesos reales -----
+// Segundos hacia adelante que se proyecta el consumo suavizado de un proceso
+const HORIZONTE_ESTIMACION: f64 = 10.0;
+// Tope de la ráfaga estimada, para que un acumulado enorme no dé un Gantt ilegible
+const RAFAGA_ESTIMADA_MAX: f64 = 100.0;
+
+// Un tick del simulador equivale a un segundo de CPU. La ráfaga es la CPU ya
+// consumida (acc_cpu_seconds) más la que consumiría en HORIZONTE_ESTIMACION segundos
+// al ritmo de su EWMA, redondeada y acotada a [1, RAFAGA_ESTIMADA_MAX]. Todos llegan
+// en 0 y con la misma prioridad. Solo sirven PIDs ya muestreados al menos una vez.
+pub fn estimar_entradas(pids: Vec<u32>) -> Result<Vec<ProcesoEntrada>, ErrorSim> {
+    let map = estado_procesos();
+    pids.into_iter()
+        .map(|pid| {
+            let stat = map.get(&pid).ok_or(ErrorSim::ProcesoNoEncontrado(pid))?;
+            let proyectada = stat.ewma_cpu.max(0.0) / 100.0 * HORIZONTE_ESTIMACION;
+            let rafaga = (stat.acc_cpu_seconds.max(0.0) + proyectada)
+                .round()
+                .clamp(1.0, RAFAGA_ESTIMADA_MAX);
+            Ok(ProcesoEntrada {
+                pid: format!("{} ({pid})", stat.name),
+                llegada: 0.0,
+                rafaga,
+                prioridad: 0,
+                rafagas: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn avance_proporcional_al_tiempo_total() {
+        assert_eq!(calcular_avance(5.0, 20.0), 25.0);
+        assert_eq!(calcular_avance(0.0, 20.0), 0.0);
+    }
+
+    #[test]
+    fn avance_sin_tiempo_total_es_cero() {
+        assert_eq!(calcular_avance(3.0, 0.0), 0.0);
+        assert_eq!(calcular_avance(0.0, 0.0), 0.0);
+        assert_eq!(calcular_avance(3.0, -10.0), 0.0);
+    }
+
+    #[test]
+    fn avance_se_acota_a_cien() {
+        assert_eq!(calcular_avance(30.0, 20.0), 100.0);
+        assert_eq!(calcular_avance(20.0, 20.0), 100.0);
+    }
+
+    #[test]
+    fn avance_con_acumulado_negativo_es_cero() {
+        assert_eq!(calcular_avance(-5.0, 20.0), 0.0);
+    }
+
+    fn proceso(pid: u32, cpu: f64) -> Proceso {
+        Proceso {
+            pid: pid.to_string(),
+            nombre: format!("p{pid}"),
+            descripcion: None,
+            ruta: String::new(),
+            cmd: Vec::new(),
+            prioridad: 0,
+            tiempo_cpu: cpu,
+            delta_cpu_seconds: 0.0,
+            cpu_normalizado: cpu,
+            cpu_suavizado: cpu,
+            memoria: 0,
+            memoria_bytes: 0,
+            memoria_humana: String::new(),
+            memoria_virtual: 0,
+            swap: 0,
+            estado: Estado::Dormido,
+            estado_codigo: None,
+            estado_etiqueta: String::new(),
+            interactividad: 0,
+            avance: 0.0,
+            iteraciones: 0,
+            tiempo_total: 0.0,
+            tiempo_restante: 0.0,
+            tiempo_inicio: 0,
+            tiempo_vida: 0.0,
+            tiempo_ejecucion: 0,
+            ppid: None,
+            protegido: false,
+            es_propio: false,
+            fijado: false,
+            es_kernel: false,
+            hilos: 1,
+            usuario: None,
+            afinidad: Vec::new(),
+            historial_cpu: Vec::new(),
+            disco_leido: 0,
+            disco_escrito: 0,
+            disco_leido_intervalo: 0,
+            disco_escrito_intervalo: 0,
+        }
+    }
+
+    fn pids(procesos: &[Proceso]) -> Vec<&str> {
+        procesos.iter().map(|p| p.pid.as_str()).collect()
+    }
+
+    #[test]
+    fn empates_de_cpu_se_ordenan_por_pid() {
+        // Mismo conjunto en dos órdenes de entrada distintos, como los daría el HashMap
+        let a = vec![
+            proceso(30, 0.0),
+            proceso(7, 5.0),
+            proceso(12, 0.0),
+            proceso(4, 0.0),
+        ];
+        let b = vec![
+            proceso(4, 0.0),
+            proceso(12, 0.0),
+            proceso(7, 5.0),
+            proceso(30, 0.0),
+        ];
+        for mut lista in [a, b] {
+            ordenar_procesos(&mut lista, CriterioOrden::Cpu, true);
+            assert_eq!(pids(&lista), vec!["7", "4", "12", "30"]);
+        }
+    }
+
+    #[test]
+    fn el_desempate_por_pid_no_se_invierte_al_descender() {
+        let mut lista = vec![proceso(9, 1.0), proceso(2, 1.0), proceso(5, 3.0)];
+        ordenar_procesos(&mut lista, CriterioOrden::Cpu, false);
+        assert_eq!(pids(&lista), vec!["2", "9", "5"]);
+        ordenar_procesos(&mut lista, CriterioOrden::Cpu, true);
+        assert_eq!(pids(&lista), vec!["5", "2", "9"]);
+    }
+
+    #[test]
+    fn top_cpu_desempata_igual_que_la_lista() {
+        let lista = vec![
+            proceso(8, 2.0),
+            proceso(3, 2.0),
+            proceso(6, 9.0),
+            proceso(1, 0.0),
+        ];
+        assert_eq!(pids(&mayores_por_cpu(lista, 3)), vec!["6", "3", "8"]);
+    }
+
+    #[test]
+    fn arbol_con_ciclo_y_autopadre_incluye_a_todos_una_vez() {
+        let con_padre = |pid: u32, ppid: u32| Proceso {
+            ppid: Some(ppid),
+            ..proceso(pid, 0.0)
+        };
+        // 1 y 2 son padres uno del otro; 3 es su propio padre; 4 cuelga del ciclo
+        let arbol = construir_arbol(vec![
+            con_padre(1, 2),
+            con_padre(2, 1),
+            con_padre(3, 3),
+            con_padre(4, 1),
+        ]);
+
+        fn aplanar(nodo: &NodoProceso) -> String {
+            let hijos: Vec<String> = nodo.hijos.iter().map(aplanar).collect();
+            if hijos.is_empty() {
+                nodo.proceso.pid.clone()
+            } else {
+                format!("{}({})", nodo.proceso.pid, hijos.join(" "))
+            }
+        }
+        let raices: Vec<String> = arbol.iter().map(aplanar).collect();
+        assert_eq!(raices, vec!["3", "1(2 4)"]);
+    }
+
+    #[test]
+    fn estimar_tiempo_total_segun_la_estrategia() {
+        let mut cfg = CONFIG.lock().unwrap().clone();
+        let mem_500_mb = 500 * 1024;
+
+        cfg.estrategia_tiempo_total = EstrategiaTiempoTotal::Cpu;
+        assert_eq!(estimar_tiempo_total(100.0, 0, &cfg), 20.0);
+        // Varios núcleos pueden pasar de 100 %: se acota a 20 s
+        assert_eq!(estimar_tiempo_total(400.0, 0, &cfg), 20.0);
+        // Sin CPU se recurre a la memoria
+        assert_eq!(estimar_tiempo_total(0.0, mem_500_mb, &cfg), 20.0);
+
+        cfg.estrategia_tiempo_total = EstrategiaTiempoTotal::Memoria;
+        assert_eq!(estimar_tiempo_total(100.0, mem_500_mb, &cfg), 20.0);
+        assert_eq!(estimar_tiempo_total(100.0, 0, &cfg), 10.0);
+
+        cfg.estrategia_tiempo_total = EstrategiaTiempoTotal::Fija(7.0);
+        assert_eq!(estimar_tiempo_total(100.0, mem_500_mb, &cfg), 7.0);
+    }
+
+    #[test]
+    fn muestra_demasiado_pronto_conserva_los_deltas_de_disco() {
+        let mut sys = System::new();
+        let pid = std::process::id();
+        assert!(sys.refresh_process(Pid::from_u32(pid)));
+        let process = sys.process(Pid::from_u32(pid)).unwrap();
+        // Ambas llamadas comparten `now`: el elapsed es 0, menor que elapsed_minimo
+        let muestra = Muestra::nueva(&sys, None).unwrap();
+
+        let (_, mut stat) = muestrear_proceso(pid, process, &muestra, None);
+        stat.disk_read_delta = 1234;
+        stat.disk_written_delta = 567;
+        stat.last_disk_read = 0;
+        stat.last_disk_written = 0;
+
+        let (proceso, repetida) = muestrear_proceso(pid, process, &muestra, Some(&stat));
+        assert_eq!(repetida.disk_read_delta, 1234);
+        assert_eq!(repetida.disk_written_delta, 567);
+        assert_eq!(repetida.last_disk_read, 0);
+        assert_eq!(repetida.last_disk_written, 0);
+        assert_eq!(proceso.disco_leido_intervalo, 1234);
+    }
+
+    #[test]
+    fn version_informa_los_procesos_que_entran_y_salen_del_filtro() {
+        let procesos = || vec![proceso(1, 5.0), proceso(2, 3.0)];
+        let solo_p1 = Filtro {
+            nombre: Some("p1".to_string()),
+            ..Filtro::default()
+        };
+        let consultar = |filtro: &Filtro, version| {
+            consultar_procesos_version(
+                procesos(),
+                filtro,
+                CriterioOrden::Pid,
+                false,
+                None,
+                None,
+                version,
+            )
+            .unwrap()
+        };
+
+        let primera = consultar(&solo_p1, None);
+        assert_eq!(pids(&primera.items), vec!["1"]);
+
+        // Sin filtro aparece 2, aunque su huella no cambió
+        let segunda = consultar(&Filtro::default(), Some(primera.version));
+        assert!(!segunda.completa);
+        assert_eq!(pids(&segunda.items), vec!["2"]);
+        assert!(segunda.eliminados.is_empty());
+
+        // Al volver al filtro, 2 se informa como eliminado
+        let tercera = consultar(&solo_p1, Some(segunda.version));
+        assert!(tercera.items.is_empty());
+        assert_eq!(tercera.eliminados, vec!["2"]);
+    }
+
+    #[test]
+    fn los_fijados_cuentan_para_el_tope() {
+        let fijado = |pid: u32, cpu: f64| Proceso {
+            fijado: true,
+            ..proceso(pid, cpu)
+        };
+        let procesos = || {
+            vec![
+                proceso(1, 90.0),
+                fijado(2, 0.0),
+                proceso(3, 50.0),
+                fijado(4, 1.0),
+            ]
+        };
+
+        // Los dos fijados entran aunque tengan poca CPU; queda un lugar para el mayor
+        let tope = aplicar_tope(procesos(), 3, &[4, 2]);
+        assert_eq!(pids(&tope), vec!["4", "2", "1"]);
+        // Con menos lugares que fijados se conservan los primeros en fijarse
+        assert_eq!(pids(&aplicar_tope(procesos(), 1, &[4, 2])), vec!["4"]);
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Instant;
+use sysinfo::{Pid, PidExt, ProcessExt, SystemExt};
+use tauri::{AppHandle, Manager};
+
+use crate::errores::ErrorSim;
+use crate::motor::{estado_procesos, ProcStat, SYSTEM};
+
+const ARCHIVO_ESTADO: &str = "estado_procesos.json";
+
+// ----- Copia serializable de ProcStat -----
+// `last_seen` es un Instant (no serializable y sin sentido entre ejecuciones); al
+// cargar se toma el instante de la carga como última muestra.
+#[derive(Serialize, Deserialize)]
+struct EstadoGuardado {
+    pid: u32,
+    start_time: u64,
+    name: String,
+    acc_cpu_seconds: f64,
+    iteraciones: u32,
+    last_cpu_positive: bool,
+    ewma_cpu: f64,
+    last_disk_read: u64,
+    last_disk_written: u64,
+}
+
+fn ruta_estado(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("No se pudo resolver el directorio de datos: {e}"))?;
+    Ok(dir.join(ARCHIVO_ESTADO))
+}
+
+// Escribe PROC_STATE en el directorio de datos de la app; devuelve cuántas entradas guardó
+pub fn guardar(app: &AppHandle) -> Result<usize, ErrorSim> {
+    let ruta = ruta_estado(app)?;
+
+    let entradas: Vec<EstadoGuardado> = {
+        let map = estado_procesos();
+        map.iter()
+            .map(|(&pid, stat)| EstadoGuardado {
+                pid,
+                start_time: stat.start_time,
+                name: stat.name.clone(),
+                acc_cpu_seconds: stat.acc_cpu_seconds,
+                iteraciones: stat.iteraciones,
+                last_cpu_positive: stat.last_cpu_positive,
+                ewma_cpu: stat.ewma_cpu,
+                last_disk_read: stat.last_disk_read,
+                last_disk_written: stat.last_disk_written,
+            })
+            .collect()
+    };
+
+    if let Some(dir) = ruta.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| ErrorSim::io(e, &format!("No se pudo crear {}", dir.display())))?;
+    }
+    let json = serde_json::to_string(&entradas).map_err(|e| e.to_string())?;
+    fs::write(&ruta, json)
+        .map_err(|e| ErrorSim::io(e, &format!("No se pudo escribir {}", ruta.display())))?;
+    Ok(entradas.len())
+}
+
+// Restaura los acumuladores guardados, descartando las entradas cuyo PID ya no existe
+// o pertenece a otro proceso (start_time distinto) y las de procesos que ya se están
+// muestreando. Devuelve cuántas se restauraron.
+pub fn cargar(app: &AppHandle) -> Result<usize, ErrorSim> {
+    let ruta = ruta_estado(app)?;
+    let json = match fs::read_to_string(&ruta) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => {
+            return Err(ErrorSim::io(
+                e,
+                &format!("No se pudo leer {}", ruta.display()),
+            ))
+        }
+    };
+    let entradas: Vec<EstadoGuardado> =
+        serde_json::from_str(&json).map_err(|e| format!("Estado guardado inválido: {e}"))?;
+
+    let mut sys = SYSTEM.lock()?;
+    sys.refresh_processes();
+    let now = Instant::now();
+
+    let mut map = estado_procesos();
+    let mut restaurados = 0;
+    for e in entradas {
+        let vigente = sys
+            .process(Pid::from_u32(e.pid))
+            .is_some_and(|p| p.start_time() == e.start_time);
+        // Un cargar_estado manual a mitad de sesión no pisa lo que ya se está midiendo
+        let en_curso = map
+            .get(&e.pid)
+            .is_some_and(|s| s.start_time == e.start_time);
+        if !vigente || en_curso {
+            continue;
+        }
+        map.insert(
+            e.pid,
+            ProcStat {
+                start_time: e.start_time,
+                name: e.name,
+                last_seen: now,
+                acc_cpu_seconds: e.acc_cpu_seconds,
+                iteraciones: e.iteraciones,
+                last_cpu_positive: e.last_cpu_positive,
+                ewma_cpu: e.ewma_cpu,
+                last_disk_read: e.last_disk_read,
+                last_disk_written: e.last_disk_written,
+                disk_read_delta: 0,
+                disk_written_delta: 0,
+                historial_cpu: VecDeque::new(),
+                en_pico: false,
+            },
+        );
+        restaurados += 1;
+    }
+    Ok(restaurados)
+}
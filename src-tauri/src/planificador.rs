@@ -0,0 +1,1666 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::errores::ErrorSim;
+
+// ----- Estructuras de entrada/salida de la simulación -----
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProcesoEntrada {
+    pub pid: String,
+    pub llegada: f64,
+    // Ráfaga única de CPU; se ignora si se indica `rafagas`
+    #[serde(default)]
+    pub rafaga: f64,
+    // Menor valor = mayor prioridad; solo lo usa el planificador por prioridad
+    #[serde(default)]
+    pub prioridad: i32,
+    // Secuencia alternada de CPU y E/S, p. ej. [CPU 5, E/S 3, CPU 2]
+    #[serde(default)]
+    pub rafagas: Vec<Rafaga>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(tag = "tipo", content = "duracion", rename_all = "lowercase")]
+pub enum Rafaga {
+    Cpu(f64),
+    Io(f64),
+}
+
+// Tolerancia al comparar instantes: con tiempos fraccionarios, sumas como 0.1 + 0.2
+// no son exactas y sin ella aparecerían tramos y esperas espurios
+pub(crate) const EPSILON: f64 = 1e-9;
+
+impl ProcesoEntrada {
+    // Ráfagas a simular: sin `rafagas` es una sola de CPU. Se descartan las vacías y
+    // se fusionan las consecutivas del mismo tipo, así CPU y E/S siempre alternan.
+    fn secuencia(&self) -> Vec<Rafaga> {
+        if self.rafagas.is_empty() {
+            return vec![Rafaga::Cpu(self.rafaga)];
+        }
+        let mut secuencia: Vec<Rafaga> = Vec::new();
+        for &rafaga in &self.rafagas {
+            match (secuencia.last_mut(), rafaga) {
+                (_, Rafaga::Cpu(d) | Rafaga::Io(d)) if d <= 0.0 => {}
+                (Some(Rafaga::Cpu(a)), Rafaga::Cpu(b)) | (Some(Rafaga::Io(a)), Rafaga::Io(b)) => {
+                    *a += b
+                }
+                _ => secuencia.push(rafaga),
+            }
+        }
+        if secuencia.is_empty() {
+            secuencia.push(Rafaga::Cpu(0.0));
+        }
+        secuencia
+    }
+
+    pub fn cpu_total(&self) -> f64 {
+        if self.rafagas.is_empty() {
+            return self.rafaga;
+        }
+        self.rafagas
+            .iter()
+            .map(|r| match r {
+                Rafaga::Cpu(d) => *d,
+                Rafaga::Io(_) => 0.0,
+            })
+            .sum()
+    }
+
+    pub fn io_total(&self) -> f64 {
+        self.rafagas
+            .iter()
+            .map(|r| match r {
+                Rafaga::Io(d) => *d,
+                Rafaga::Cpu(_) => 0.0,
+            })
+            .sum()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ResultadoProceso {
+    pub pid: String,
+    pub llegada: f64,
+    pub rafaga: f64,
+    pub prioridad: i32,
+    // Mejor prioridad efectiva alcanzada por envejecimiento (igual a `prioridad` si no hubo)
+    pub prioridad_envejecida: i32,
+    pub inicio: f64,
+    pub fin: f64,
+    pub espera: f64,
+    pub retorno: f64,
+    // Desde la llegada hasta el primer despacho
+    pub respuesta: f64,
+    // Cola en la que terminó el proceso; solo la informa MLFQ
+    pub nivel_final: Option<u32>,
+}
+
+// Tramo contiguo de la línea de tiempo ocupado por un proceso (o por la CPU ociosa)
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SegmentoGantt {
+    pub pid: String,
+    pub inicio: f64,
+    pub fin: f64,
+}
+
+pub const PID_OCIOSO: &str = "idle";
+// Tramos de CPU gastados en cambios de contexto, sin proceso asignado
+pub const PID_CAMBIO_CONTEXTO: &str = "cc";
+
+#[derive(Serialize, Deserialize)]
+pub struct ResultadoSimulacion {
+    pub procesos: Vec<ResultadoProceso>,
+    pub gantt: Vec<SegmentoGantt>,
+    // Intervalos de E/S de cada proceso. Van aparte del Gantt porque se solapan con
+    // la CPU: mientras uno está bloqueado, otro puede ejecutarse.
+    #[serde(default)]
+    pub bloqueos: Vec<SegmentoGantt>,
+    // Uso del dispositivo de E/S único (vacío si la E/S no compite por un dispositivo)
+    #[serde(default)]
+    pub gantt_io: Vec<SegmentoGantt>,
+    pub espera_promedio: f64,
+    pub retorno_promedio: f64,
+    pub respuesta_promedio: f64,
+    // Desviación estándar poblacional de cada métrica
+    pub espera_desviacion: f64,
+    pub retorno_desviacion: f64,
+    pub respuesta_desviacion: f64,
+    pub cambios_contexto: u32,
+    // CPU gastada en cambios de contexto (tramos PID_CAMBIO_CONTEXTO del Gantt)
+    #[serde(default)]
+    pub tiempo_cambio_contexto: f64,
+    // Eficiencia de la CPU entre la primera llegada y el último fin
+    pub tiempo_ocupado: f64,
+    pub tiempo_ocioso: f64,
+    // Porcentaje (0-100) de esa ventana con la CPU ocupada
+    pub utilizacion: f64,
+    // Veces que se desalojó a un proceso antes de terminar (0 en los no expropiativos)
+    pub expropiaciones: u32,
+    // Primer despacho y último fin, los límites exactos del eje de tiempo
+    #[serde(default)]
+    pub primer_inicio: f64,
+    #[serde(default)]
+    pub ultimo_fin: f64,
+    // Desde la primera llegada hasta el último fin; el hueco previo a la primera
+    // llegada no cuenta
+    #[serde(default)]
+    pub makespan: f64,
+}
+
+// ----- Datos de demostración -----
+// Misma semilla, mismos procesos: StdRng es determinista para una versión fija de
+// rand (la del Cargo.lock). Las llegadas se reparten en una ventana que crece con `n`
+// para que haya tanto solapamiento como huecos.
+pub fn procesos_demo(n: usize, semilla: u64) -> Vec<ProcesoEntrada> {
+    let mut rng = StdRng::seed_from_u64(semilla);
+    let ventana = (n as u32).saturating_mul(2);
+    (1..=n)
+        .map(|i| ProcesoEntrada {
+            pid: format!("P{i}"),
+            llegada: f64::from(rng.gen_range(0..=ventana)),
+            rafaga: f64::from(rng.gen_range(1..=10u32)),
+            prioridad: rng.gen_range(0..=9),
+            rafagas: Vec::new(),
+        })
+        .collect()
+}
+
+// ----- Casos de enseñanza -----
+// Efecto convoy: un trabajo largo llega primero (en el mismo instante, pero antes en
+// la entrada) y los cortos quedan esperando detrás de él en FIFO. SJF los atiende
+// primero y la espera promedio cae de 22.6 a 3.8.
+pub fn caso_convoy() -> Vec<ProcesoEntrada> {
+    [
+        ("P1", 24.0),
+        ("P2", 3.0),
+        ("P3", 3.0),
+        ("P4", 2.0),
+        ("P5", 1.0),
+    ]
+    .into_iter()
+    .map(|(pid, rafaga)| ProcesoEntrada {
+        pid: pid.to_string(),
+        llegada: 0.0,
+        rafaga,
+        prioridad: 0,
+        rafagas: Vec::new(),
+    })
+    .collect()
+}
+
+// ----- Distribución de ráfagas -----
+// Histograma del CPU total de cada proceso en `num_buckets` intervalos de igual
+// ancho: (inicio, fin, conteo). El último intervalo incluye su fin. Si todas las
+// ráfagas son iguales no hay ancho que repartir y se devuelve un único intervalo.
+pub fn distribucion_bursts(
+    procesos: &[ProcesoEntrada],
+    num_buckets: usize,
+) -> Vec<(f64, f64, usize)> {
+    let rafagas: Vec<f64> = procesos.iter().map(ProcesoEntrada::cpu_total).collect();
+    let Some(minimo) = rafagas.iter().copied().reduce(f64::min) else {
+        return Vec::new();
+    };
+    let maximo = rafagas.iter().copied().fold(minimo, f64::max);
+    if maximo - minimo <= EPSILON {
+        return vec![(minimo, maximo, rafagas.len())];
+    }
+
+    let n = num_buckets.max(1);
+    let ancho = (maximo - minimo) / n as f64;
+    let mut conteos = vec![0; n];
+    for r in rafagas {
+        let k = ((r - minimo) / ancho + EPSILON).floor() as usize;
+        conteos[k.min(n - 1)] += 1;
+    }
+    conteos
+        .into_iter()
+        .enumerate()
+        .map(|(k, conteo)| {
+            let fin = if k == n - 1 {
+                maximo
+            } else {
+                minimo + (k + 1) as f64 * ancho
+            };
+            (minimo + k as f64 * ancho, fin, conteo)
+        })
+        .collect()
+}
+
+// ----- Selección de algoritmo -----
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Algoritmo {
+    Fifo,
+    // FIFO que, entre llegadas simultáneas, atiende primero a la de mejor prioridad
+    FifoPrioridad,
+    RoundRobin,
+    Sjf,
+    Ljf,
+    Srtf,
+    Prioridad,
+    PrioridadExpropiativa,
+}
+
+impl Algoritmo {
+    // Solo FIFO y Round Robin bloquean a un proceso durante sus ráfagas de E/S; el
+    // resto las descarta y corre la CPU total de corrido
+    pub fn modela_io(self) -> bool {
+        matches!(self, Algoritmo::Fifo | Algoritmo::RoundRobin)
+    }
+}
+
+// Ejecuta el planificador indicado; `quantum` solo lo usa Round Robin y
+// `costo_cambio_contexto` solo los expropiativos
+pub fn ejecutar(
+    procesos: &[ProcesoEntrada],
+    algoritmo: Algoritmo,
+    quantum: f64,
+    costo_cambio_contexto: f64,
+) -> ResultadoSimulacion {
+    let costo = costo_cambio_contexto;
+    match algoritmo {
+        Algoritmo::Fifo => fifo(procesos),
+        Algoritmo::FifoPrioridad => fifo_prioridad(procesos),
+        Algoritmo::RoundRobin => round_robin(procesos, quantum, costo),
+        Algoritmo::Sjf => sjf(procesos),
+        Algoritmo::Ljf => ljf(procesos),
+        Algoritmo::Srtf => srtf(procesos, costo),
+        Algoritmo::Prioridad => prioridad(procesos, false, None, costo),
+        Algoritmo::PrioridadExpropiativa => prioridad(procesos, true, None, costo),
+    }
+}
+
+// ----- Validación de las entradas -----
+// Rechaza entradas que el planificador resolvería en silencio con un Gantt sin
+// sentido: ráfagas nulas o negativas, llegadas negativas y PIDs repetidos
+pub fn validar_entrada(procesos: &[ProcesoEntrada]) -> Result<(), ErrorSim> {
+    let mut vistos = HashSet::new();
+    for (i, p) in procesos.iter().enumerate() {
+        let error = |motivo: String| {
+            Err(ErrorSim::EntradaInvalida(format!(
+                "Proceso {} (posición {}): {motivo}",
+                p.pid,
+                i + 1
+            )))
+        };
+        if !vistos.insert(p.pid.as_str()) {
+            return error("el PID está repetido".to_string());
+        }
+        if !p.llegada.is_finite() || p.llegada < 0.0 {
+            return error(format!("llegada inválida ({})", p.llegada));
+        }
+        if p.rafagas.is_empty() {
+            if !p.rafaga.is_finite() || p.rafaga <= 0.0 {
+                return error(format!("la ráfaga debe ser positiva ({})", p.rafaga));
+            }
+            continue;
+        }
+        for (j, rafaga) in p.rafagas.iter().enumerate() {
+            let (tipo, d) = match *rafaga {
+                Rafaga::Cpu(d) => ("CPU", d),
+                Rafaga::Io(d) => ("E/S", d),
+            };
+            if !d.is_finite() || d <= 0.0 {
+                return error(format!(
+                    "la ráfaga {} ({tipo}) debe ser positiva ({d})",
+                    j + 1
+                ));
+            }
+        }
+        if !p.rafagas.iter().any(|r| matches!(r, Rafaga::Cpu(_))) {
+            return error("no tiene ninguna ráfaga de CPU".to_string());
+        }
+    }
+    Ok(())
+}
+
+// Un quantum no positivo o no finito nunca avanzaría el reloj. quantum_valido lo
+// cambia por 1 en silencio; los comandos prefieren avisar.
+pub fn validar_quantum(quantum: f64) -> Result<f64, ErrorSim> {
+    if quantum > 0.0 && quantum.is_finite() {
+        Ok(quantum)
+    } else {
+        Err(ErrorSim::EntradaInvalida(format!(
+            "El quantum debe ser positivo ({quantum})"
+        )))
+    }
+}
+
+// Los planificadores que no modelan E/S la descartarían en silencio; se rechaza para
+// que el Gantt no parezca incluirla
+pub fn validar_sin_io(procesos: &[ProcesoEntrada], algoritmo: &str) -> Result<(), ErrorSim> {
+    match procesos.iter().find(|p| p.io_total() > 0.0) {
+        Some(p) => Err(ErrorSim::EntradaInvalida(format!(
+            "Proceso {}: {algoritmo} no modela ráfagas de E/S",
+            p.pid
+        ))),
+        None => Ok(()),
+    }
+}
+
+// Round Robin no tiene un quantum razonable por defecto, así que se exige explícito;
+// para los demás algoritmos el valor no se usa
+pub fn quantum_para(algoritmos: &[Algoritmo], quantum: Option<f64>) -> Result<f64, ErrorSim> {
+    match quantum {
+        Some(q) if algoritmos.contains(&Algoritmo::RoundRobin) => validar_quantum(q),
+        Some(q) => Ok(q),
+        None if algoritmos.contains(&Algoritmo::RoundRobin) => Err(ErrorSim::EntradaInvalida(
+            "Round Robin requiere un quantum".to_string(),
+        )),
+        None => Ok(0.0),
+    }
+}
+
+// Corre la misma entrada por cada algoritmo pedido, para compararlos lado a lado
+pub fn comparar(
+    procesos: &[ProcesoEntrada],
+    algoritmos: Vec<Algoritmo>,
+    quantum: Option<f64>,
+    costo_cambio_contexto: f64,
+) -> Result<Vec<(Algoritmo, ResultadoSimulacion)>, ErrorSim> {
+    validar_entrada(procesos)?;
+    if algoritmos.iter().any(|a| !a.modela_io()) {
+        validar_sin_io(procesos, "Alguno de los algoritmos pedidos")?;
+    }
+    let quantum = quantum_para(&algoritmos, quantum)?;
+    Ok(algoritmos
+        .into_iter()
+        .map(|a| (a, ejecutar(procesos, a, quantum, costo_cambio_contexto)))
+        .collect())
+}
+
+// ----- FIFO (First Come, First Served) -----
+pub fn fifo(procesos: &[ProcesoEntrada]) -> ResultadoSimulacion {
+    cola_de_listos(procesos, None, false, 0.0)
+}
+
+// Variante no expropiativa de FIFO: a igual llegada decide la prioridad (menor valor
+// primero) en lugar del orden de entrada. No modela E/S.
+pub fn fifo_prioridad(procesos: &[ProcesoEntrada]) -> ResultadoSimulacion {
+    no_expropiativo(procesos, |a, b| {
+        a.llegada
+            .total_cmp(&b.llegada)
+            .then(a.prioridad.cmp(&b.prioridad))
+    })
+}
+
+// ----- Round Robin -----
+pub fn round_robin(
+    procesos: &[ProcesoEntrada],
+    quantum: f64,
+    costo_cambio_contexto: f64,
+) -> ResultadoSimulacion {
+    cola_de_listos(
+        procesos,
+        Some(quantum_valido(quantum)),
+        false,
+        costo_cambio_contexto,
+    )
+}
+
+// ----- E/S con dispositivo único -----
+// FIFO (o Round Robin si hay quantum) donde todas las E/S comparten un mismo
+// dispositivo atendido en orden de solicitud: un proceso que pide E/S con el
+// dispositivo ocupado queda bloqueado en su cola hasta que se libere.
+pub fn con_dispositivo_io(
+    procesos: &[ProcesoEntrada],
+    quantum: Option<f64>,
+    costo_cambio_contexto: f64,
+) -> ResultadoSimulacion {
+    cola_de_listos(
+        procesos,
+        quantum.map(quantum_valido),
+        true,
+        costo_cambio_contexto,
+    )
+}
+
+// Un quantum no positivo (o NaN) nunca avanzaría el reloj; se usa 1 en su lugar
+pub(crate) fn quantum_valido(quantum: f64) -> f64 {
+    if quantum > 0.0 && quantum.is_finite() {
+        quantum
+    } else {
+        1.0
+    }
+}
+
+// Costo de cambio de contexto utilizable; negativo o no finito equivale a 0
+fn costo_valido(costo: f64) -> f64 {
+    if costo > 0.0 && costo.is_finite() {
+        costo
+    } else {
+        0.0
+    }
+}
+
+// Cada cambio entre dos procesos distintos ocupa `costo` de CPU antes de que el nuevo
+// empiece a ejecutarse. El cambio no se interrumpe: lo que llegue mientras tanto se
+// considera en el siguiente punto de decisión.
+fn cambiar_contexto(gantt: &mut Vec<SegmentoGantt>, reloj: &mut f64, costo: f64) {
+    registrar(gantt, PID_CAMBIO_CONTEXTO, *reloj, *reloj + costo);
+    *reloj += costo;
+}
+
+// Cola de listos FIFO compartida por FIFO (sin quantum) y Round Robin. Al terminar
+// una ráfaga de CPU seguida de E/S, el proceso queda bloqueado y libera la CPU;
+// vuelve al final de la cola cuando su E/S termina. El reloj salta de evento en
+// evento (llegada, fin de quantum, fin de ráfaga o de E/S), sin pasos fijos. Con
+// `dispositivo_unico` las E/S se atienden de a una; si no, todas en paralelo.
+fn cola_de_listos(
+    procesos: &[ProcesoEntrada],
+    quantum: Option<f64>,
+    dispositivo_unico: bool,
+    costo_cambio_contexto: f64,
+) -> ResultadoSimulacion {
+    let costo = costo_valido(costo_cambio_contexto);
+    let n = procesos.len();
+    let secuencias: Vec<Vec<Rafaga>> = procesos.iter().map(ProcesoEntrada::secuencia).collect();
+    let mut paso = vec![0usize; n];
+    // Restante de la ráfaga de CPU en curso
+    let mut restante = vec![0.0; n];
+    // Instante en que el proceso pasa a la cola de listos y si es por fin de E/S
+    let mut listo_en: Vec<Option<(f64, bool)>> = vec![None; n];
+    let mut inicio: Vec<Option<f64>> = vec![None; n];
+    let mut fin = vec![0.0; n];
+    let mut cola: VecDeque<usize> = VecDeque::new();
+    let mut gantt = Vec::new();
+    let mut bloqueos = Vec::new();
+    // Solicitudes de E/S pendientes de atender: (instante, proceso, duración)
+    let mut solicitudes: Vec<(f64, usize, f64)> = Vec::new();
+    // Instante en que se libera el dispositivo único (None si no hay)
+    let mut libre = dispositivo_unico.then_some(0.0);
+    let mut gantt_io = Vec::new();
+    // Tiempo esperando el dispositivo, que cuenta como bloqueo y no como espera
+    let mut espera_io = vec![0.0; n];
+    let mut desalojado: Option<usize> = None;
+    let mut reloj = 0.0;
+    let mut ultimo: Option<usize> = None;
+    let mut cambios_contexto = 0u32;
+    let mut terminados = 0;
+
+    // Un proceso que empieza con E/S la solicita al llegar
+    for (i, p) in procesos.iter().enumerate() {
+        match secuencias[i][0] {
+            Rafaga::Cpu(d) => {
+                restante[i] = d;
+                listo_en[i] = Some((p.llegada, false));
+            }
+            Rafaga::Io(d) => solicitudes.push((p.llegada, i, d)),
+        }
+    }
+
+    // Encola lo que quedó listo hasta `reloj` en orden cronológico; a igual instante
+    // entran primero las llegadas y luego los fines de E/S, y entre llegadas se
+    // respeta el orden de llegada y de entrada.
+    let admitir_listos =
+        |reloj: f64, listo_en: &mut [Option<(f64, bool)>], cola: &mut VecDeque<usize>| {
+            let mut listos: Vec<usize> = (0..n)
+                .filter(|&i| listo_en[i].is_some_and(|(t, _)| t <= reloj + EPSILON))
+                .collect();
+            listos.sort_by(|&a, &b| {
+                let (ta, io_a) = listo_en[a].unwrap_or_default();
+                let (tb, io_b) = listo_en[b].unwrap_or_default();
+                ta.total_cmp(&tb)
+                    .then(io_a.cmp(&io_b))
+                    .then(procesos[a].llegada.total_cmp(&procesos[b].llegada))
+                    .then(a.cmp(&b))
+            });
+            for i in listos {
+                listo_en[i] = None;
+                cola.push_back(i);
+            }
+        };
+
+    while terminados < n {
+        // Las solicitudes de E/S hechas hasta `reloj` se atienden en orden cronológico,
+        // así el dispositivo único las toma en el orden en que se pidieron
+        solicitudes.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        let atendidas = solicitudes
+            .iter()
+            .take_while(|&&(t, _, _)| t <= reloj + EPSILON)
+            .count();
+        for (t, i, d) in solicitudes.drain(..atendidas).collect::<Vec<_>>() {
+            let comienzo = libre.map_or(t, |l: f64| l.max(t));
+            let fin_io = comienzo + d;
+            if let Some(l) = libre.as_mut() {
+                *l = fin_io;
+                espera_io[i] += comienzo - t;
+                registrar(&mut gantt_io, &procesos[i].pid, comienzo, fin_io);
+            }
+            bloqueos.push(SegmentoGantt {
+                pid: procesos[i].pid.clone(),
+                inicio: t,
+                fin: fin_io,
+            });
+            paso[i] += 1;
+            match secuencias[i].get(paso[i]) {
+                Some(&Rafaga::Cpu(c)) => {
+                    restante[i] = c;
+                    listo_en[i] = Some((fin_io, true));
+                }
+                // Una E/S final también cuenta para el retorno
+                _ => {
+                    inicio[i].get_or_insert(t);
+                    fin[i] = fin_io;
+                    terminados += 1;
+                }
+            }
+        }
+
+        admitir_listos(reloj, &mut listo_en, &mut cola);
+        // El desalojado vuelve detrás de lo que llegó durante (o justo al final de) su
+        // corrida
+        if let Some(d) = desalojado.take() {
+            cola.push_back(d);
+        }
+
+        let Some(i) = cola.pop_front() else {
+            // CPU ociosa hasta la próxima llegada, solicitud o fin de E/S
+            let Some(proximo) = listo_en
+                .iter()
+                .flatten()
+                .map(|&(t, _)| t)
+                .chain(solicitudes.iter().map(|&(t, _, _)| t))
+                .reduce(f64::min)
+            else {
+                break;
+            };
+            registrar(&mut gantt, PID_OCIOSO, reloj, proximo);
+            reloj = proximo;
+            continue;
+        };
+
+        if ultimo.is_some_and(|u| u != i) {
+            cambios_contexto += 1;
+            cambiar_contexto(&mut gantt, &mut reloj, costo);
+        }
+        ultimo = Some(i);
+        inicio[i].get_or_insert(reloj);
+
+        let corrida = quantum.map_or(restante[i], |q| q.min(restante[i]));
+        registrar(&mut gantt, &procesos[i].pid, reloj, reloj + corrida);
+        reloj += corrida;
+        restante[i] -= corrida;
+
+        if restante[i] > EPSILON {
+            desalojado = Some(i);
+            continue;
+        }
+
+        // Ráfaga de CPU terminada: sigue una de E/S (alternan) o el proceso termina
+        paso[i] += 1;
+        match secuencias[i].get(paso[i]) {
+            Some(&Rafaga::Io(d)) => solicitudes.push((reloj, i, d)),
+            _ => {
+                fin[i] = reloj;
+                terminados += 1;
+            }
+        }
+    }
+
+    let inicio: Vec<f64> = inicio.into_iter().map(|t| t.unwrap_or(0.0)).collect();
+    let mut resultado = resumir(procesos, &inicio, &fin, gantt, bloqueos, cambios_contexto);
+    if dispositivo_unico {
+        for (r, e) in resultado.procesos.iter_mut().zip(&espera_io) {
+            r.espera = (r.espera - e).max(0.0);
+        }
+        (resultado.espera_promedio, resultado.espera_desviacion) =
+            estadisticas(resultado.procesos.iter().map(|r| r.espera));
+        resultado.gantt_io = gantt_io;
+    }
+    resultado
+}
+
+// Copia de los procesos con la CPU total como única ráfaga, para los planificadores
+// que no modelan bloqueos por E/S
+fn sin_io(procesos: &[ProcesoEntrada]) -> Vec<ProcesoEntrada> {
+    procesos
+        .iter()
+        .map(|p| ProcesoEntrada {
+            rafaga: p.cpu_total(),
+            rafagas: Vec::new(),
+            ..p.clone()
+        })
+        .collect()
+}
+
+// ----- SJF (Shortest Job First, no expropiativo) -----
+pub fn sjf(procesos: &[ProcesoEntrada]) -> ResultadoSimulacion {
+    no_expropiativo(procesos, |a, b| a.rafaga.total_cmp(&b.rafaga))
+}
+
+// ----- LJF (Longest Job First, no expropiativo) -----
+pub fn ljf(procesos: &[ProcesoEntrada]) -> ResultadoSimulacion {
+    no_expropiativo(procesos, |a, b| b.rafaga.total_cmp(&a.rafaga))
+}
+
+// ----- SRTF (Shortest Remaining Time First, SJF expropiativo) -----
+pub fn srtf(procesos: &[ProcesoEntrada], costo_cambio_contexto: f64) -> ResultadoSimulacion {
+    let procesos = &sin_io(procesos);
+    let costo = costo_valido(costo_cambio_contexto);
+    let n = procesos.len();
+    let mut restante: Vec<f64> = procesos.iter().map(|p| p.rafaga).collect();
+    let mut terminado = vec![false; n];
+    let mut inicio: Vec<Option<f64>> = vec![None; n];
+    let mut fin = vec![0.0; n];
+    let mut gantt = Vec::new();
+    let mut reloj = 0.0;
+    let mut actual: Option<usize> = None;
+    let mut ultimo: Option<usize> = None;
+    let mut cambios_contexto = 0u32;
+    let mut terminados = 0;
+
+    while terminados < n {
+        let mejor = primero_listo(
+            procesos,
+            reloj,
+            |i| !terminado[i],
+            |a, b| restante[a].total_cmp(&restante[b]),
+        );
+
+        // Solo se desaloja al proceso en curso si hay otro con restante estrictamente
+        // menor; con restantes iguales sigue el que ya tenía la CPU.
+        actual = match (actual, mejor) {
+            (Some(a), Some(m)) if restante[m] >= restante[a] - EPSILON => Some(a),
+            (_, m) => m,
+        };
+
+        let Some(i) = actual else {
+            // CPU ociosa hasta la próxima llegada
+            let llegada = proxima_llegada(procesos, reloj, |j| !terminado[j]).unwrap_or(reloj);
+            registrar(&mut gantt, PID_OCIOSO, reloj, llegada);
+            reloj = llegada;
+            continue;
+        };
+
+        if ultimo.is_some_and(|u| u != i) {
+            cambios_contexto += 1;
+            cambiar_contexto(&mut gantt, &mut reloj, costo);
+        }
+        ultimo = Some(i);
+        inicio[i].get_or_insert(reloj);
+
+        // La decisión solo puede cambiar en la próxima llegada, así que se ejecuta
+        // hasta entonces (o hasta terminar) en lugar de tick a tick.
+        let corrida = match proxima_llegada(procesos, reloj, |_| true) {
+            Some(t) => restante[i].min(t - reloj),
+            None => restante[i],
+        };
+        registrar(&mut gantt, &procesos[i].pid, reloj, reloj + corrida);
+        reloj += corrida;
+        restante[i] -= corrida;
+
+        if restante[i] <= EPSILON {
+            terminado[i] = true;
+            terminados += 1;
+            fin[i] = reloj;
+            actual = None;
+        }
+    }
+
+    let inicio: Vec<f64> = inicio.into_iter().map(|t| t.unwrap_or(0.0)).collect();
+    resumir(procesos, &inicio, &fin, gantt, Vec::new(), cambios_contexto)
+}
+
+// ----- Planificación por prioridad -----
+// Menor valor numérico = mayor prioridad. Con `envejecimiento = Some(t)`, cada `t`
+// unidades de tiempo esperando en la cola mejoran en 1 la prioridad efectiva del
+// proceso; al recibir la CPU vuelve a su prioridad original. En modo expropiativo, un
+// proceso listo con prioridad efectiva estrictamente mejor desaloja al que está en curso.
+pub fn prioridad(
+    procesos: &[ProcesoEntrada],
+    expropiativo: bool,
+    envejecimiento: Option<f64>,
+    costo_cambio_contexto: f64,
+) -> ResultadoSimulacion {
+    let procesos = &sin_io(procesos);
+    let costo = costo_valido(costo_cambio_contexto);
+    let n = procesos.len();
+    let envejecimiento = envejecimiento.filter(|&t| t > 0.0 && t.is_finite());
+    let mut restante: Vec<f64> = procesos.iter().map(|p| p.rafaga).collect();
+    let mut terminado = vec![false; n];
+    let mut espera_cola = vec![0.0; n];
+    let mut envejecida: Vec<i32> = procesos.iter().map(|p| p.prioridad).collect();
+    let mut inicio: Vec<Option<f64>> = vec![None; n];
+    let mut fin = vec![0.0; n];
+    let mut gantt = Vec::new();
+    let mut reloj = 0.0;
+    let mut actual: Option<usize> = None;
+    let mut ultimo: Option<usize> = None;
+    let mut cambios_contexto = 0u32;
+    let mut terminados = 0;
+
+    let efectiva = |i: usize, espera_cola: &[f64]| -> i32 {
+        let bono = envejecimiento.map_or(0.0, |t| (espera_cola[i] / t + EPSILON).floor());
+        procesos[i].prioridad.saturating_sub(bono as i32)
+    };
+
+    while terminados < n {
+        let mejor = primero_listo(
+            procesos,
+            reloj,
+            |i| !terminado[i],
+            |a, b| efectiva(a, &espera_cola).cmp(&efectiva(b, &espera_cola)),
+        );
+
+        actual = match (actual, mejor) {
+            (Some(a), _) if !expropiativo => Some(a),
+            (Some(a), Some(m)) if efectiva(m, &espera_cola) >= efectiva(a, &espera_cola) => Some(a),
+            (_, m) => m,
+        };
+
+        let Some(i) = actual else {
+            // CPU ociosa hasta la próxima llegada
+            let llegada = proxima_llegada(procesos, reloj, |j| !terminado[j]).unwrap_or(reloj);
+            registrar(&mut gantt, PID_OCIOSO, reloj, llegada);
+            reloj = llegada;
+            continue;
+        };
+
+        // El cambio de contexto también cuenta como espera para el envejecimiento
+        let desde = reloj;
+        if ultimo.is_some_and(|u| u != i) {
+            cambios_contexto += 1;
+            cambiar_contexto(&mut gantt, &mut reloj, costo);
+        }
+        ultimo = Some(i);
+        inicio[i].get_or_insert(reloj);
+
+        // Sin expropiación la decisión no cambia hasta que termine. Con ella, solo
+        // puede cambiar en la próxima llegada o cuando algún proceso en espera cruce
+        // el siguiente escalón de envejecimiento.
+        let mut corrida = restante[i];
+        if expropiativo {
+            if let Some(t) = proxima_llegada(procesos, reloj, |_| true) {
+                corrida = corrida.min(t - reloj);
+            }
+            if let Some(t) = envejecimiento {
+                for j in 0..n {
+                    if j != i && !terminado[j] && procesos[j].llegada <= reloj + EPSILON {
+                        let acumulada = espera_cola[j] + reloj - procesos[j].llegada.max(desde);
+                        let escalon = t - acumulada.rem_euclid(t);
+                        if escalon > EPSILON {
+                            corrida = corrida.min(escalon);
+                        }
+                    }
+                }
+            }
+        }
+
+        registrar(&mut gantt, &procesos[i].pid, reloj, reloj + corrida);
+        let hasta = reloj + corrida;
+        // Cada proceso en espera acumula el tramo de la corrida posterior a su llegada
+        for j in 0..n {
+            if j != i && !terminado[j] && procesos[j].llegada < hasta {
+                espera_cola[j] += hasta - procesos[j].llegada.max(desde);
+                envejecida[j] = envejecida[j].min(efectiva(j, &espera_cola));
+            }
+        }
+        espera_cola[i] = 0.0;
+        reloj = hasta;
+        restante[i] -= corrida;
+
+        if restante[i] <= EPSILON {
+            terminado[i] = true;
+            terminados += 1;
+            fin[i] = reloj;
+            actual = None;
+        }
+    }
+
+    let inicio: Vec<f64> = inicio.into_iter().map(|t| t.unwrap_or(0.0)).collect();
+    let mut resultado = resumir(procesos, &inicio, &fin, gantt, Vec::new(), cambios_contexto);
+    for (registro, &p) in resultado.procesos.iter_mut().zip(&envejecida) {
+        registro.prioridad_envejecida = p;
+    }
+    resultado
+}
+
+// ----- MLFQ (Multilevel Feedback Queue) -----
+// `quantums[k]` es el quantum de la cola k; la cola 0 es la más prioritaria. Reglas:
+// - Todo proceso llega a la cola 0 y siempre se atiende la cola no vacía más alta.
+// - Quien agota el quantum de su cola baja un nivel (en la última se queda, en
+//   Round Robin); quien termina antes no baja.
+// - Convención para llegadas a mitad de quantum: como entran a la cola 0, desalojan
+//   en ese mismo instante a un proceso de una cola inferior. El desalojado conserva
+//   su nivel y vuelve al frente de su cola con el resto de su quantum. Si el proceso
+//   en curso ya está en la cola 0, la llegada espera a que termine su quantum.
+// - Con `periodo_boost = Some(p)`, en cada múltiplo de `p` todos los procesos pendientes
+//   suben a la cola 0 (conservando su orden) y se les reinicia el quantum consumido;
+//   así ninguno queda postergado indefinidamente.
+pub fn mlfq(
+    procesos: &[ProcesoEntrada],
+    quantums: &[f64],
+    periodo_boost: Option<f64>,
+    costo_cambio_contexto: f64,
+) -> ResultadoSimulacion {
+    let procesos = &sin_io(procesos);
+    let costo = costo_valido(costo_cambio_contexto);
+    let n = procesos.len();
+    let quantums: Vec<f64> = quantums.iter().map(|&q| quantum_valido(q)).collect();
+    let niveles = quantums.len().max(1);
+    let quantum = |nivel: usize| quantums.get(nivel).copied().unwrap_or(1.0);
+    let periodo_boost = periodo_boost.filter(|&p| p > 0.0 && p.is_finite());
+    let orden = orden_llegada(procesos);
+
+    let mut colas: Vec<VecDeque<usize>> = vec![VecDeque::new(); niveles];
+    let mut nivel = vec![0usize; n];
+    let mut usado = vec![0.0; n];
+    let mut restante: Vec<f64> = procesos.iter().map(|p| p.rafaga).collect();
+    let mut inicio: Vec<Option<f64>> = vec![None; n];
+    let mut fin = vec![0.0; n];
+    let mut gantt = Vec::new();
+    let mut siguiente = 0;
+    let mut reloj = 0.0;
+    let mut proximo_boost = periodo_boost;
+    let mut ultimo: Option<usize> = None;
+    let mut cambios_contexto = 0u32;
+
+    loop {
+        admitir(procesos, &orden, &mut siguiente, reloj, &mut colas[0]);
+
+        if let (Some(periodo), Some(boost)) = (periodo_boost, proximo_boost) {
+            if reloj >= boost - EPSILON {
+                let pendientes: Vec<usize> = colas.iter_mut().flat_map(|c| c.drain(..)).collect();
+                for &i in &pendientes {
+                    nivel[i] = 0;
+                    usado[i] = 0.0;
+                }
+                colas[0].extend(pendientes);
+                // Si la CPU estuvo ociosa pudieron pasar varios períodos
+                proximo_boost = Some(((reloj / periodo + EPSILON).floor() + 1.0) * periodo);
+            }
+        }
+
+        let Some(i) = colas.iter_mut().find_map(|c| c.pop_front()) else {
+            if siguiente < orden.len() {
+                // CPU ociosa hasta la próxima llegada
+                let llegada = procesos[orden[siguiente]].llegada;
+                registrar(&mut gantt, PID_OCIOSO, reloj, llegada);
+                reloj = llegada;
+                continue;
+            }
+            break;
+        };
+        let k = nivel[i];
+
+        if ultimo.is_some_and(|u| u != i) {
+            cambios_contexto += 1;
+            cambiar_contexto(&mut gantt, &mut reloj, costo);
+            // Lo que llegó durante el cambio espera en la cola 0
+            admitir(procesos, &orden, &mut siguiente, reloj, &mut colas[0]);
+        }
+        ultimo = Some(i);
+        inicio[i].get_or_insert(reloj);
+
+        let mut corrida = (quantum(k) - usado[i]).min(restante[i]);
+        // Una llegada solo interrumpe a procesos de colas inferiores a la 0
+        if k > 0 && siguiente < orden.len() {
+            corrida = corrida.min(procesos[orden[siguiente]].llegada - reloj);
+        }
+        // Un boost vencido durante el cambio de contexto se aplica al terminar la corrida
+        if let Some(boost) = proximo_boost.filter(|&b| b > reloj + EPSILON) {
+            corrida = corrida.min(boost - reloj);
+        }
+
+        registrar(&mut gantt, &procesos[i].pid, reloj, reloj + corrida);
+        reloj += corrida;
+        restante[i] -= corrida;
+        usado[i] += corrida;
+
+        // Igual que en Round Robin, las llegadas entran antes de reencolar al desalojado
+        admitir(procesos, &orden, &mut siguiente, reloj, &mut colas[0]);
+        if restante[i] <= EPSILON {
+            fin[i] = reloj;
+        } else if usado[i] >= quantum(k) - EPSILON {
+            nivel[i] = (k + 1).min(niveles - 1);
+            usado[i] = 0.0;
+            colas[nivel[i]].push_back(i);
+        } else {
+            // Desalojado por una llegada o por el boost antes de agotar su quantum
+            colas[k].push_front(i);
+        }
+    }
+
+    let inicio: Vec<f64> = inicio.into_iter().map(|t| t.unwrap_or(0.0)).collect();
+    let mut resultado = resumir(procesos, &inicio, &fin, gantt, Vec::new(), cambios_contexto);
+    for (registro, &k) in resultado.procesos.iter_mut().zip(&nivel) {
+        registro.nivel_final = Some(k as u32);
+    }
+    resultado
+}
+
+// Núcleo compartido por SJF, LJF y FIFO por prioridad: en cada punto de despacho
+// elige, entre los procesos que ya llegaron, el primero según `comparar` y lo ejecuta
+// completo; el reloj salta directamente al fin de su ráfaga.
+fn no_expropiativo(
+    procesos: &[ProcesoEntrada],
+    comparar: impl Fn(&ProcesoEntrada, &ProcesoEntrada) -> Ordering,
+) -> ResultadoSimulacion {
+    let procesos = &sin_io(procesos);
+    let n = procesos.len();
+    let mut pendiente = vec![true; n];
+    let mut inicio = vec![0.0; n];
+    let mut fin = vec![0.0; n];
+    let mut gantt = Vec::new();
+    let mut reloj = 0.0;
+    let mut despachados = 0;
+
+    while despachados < n {
+        let elegido = primero_listo(
+            procesos,
+            reloj,
+            |i| pendiente[i],
+            |a, b| comparar(&procesos[a], &procesos[b]),
+        );
+
+        let Some(i) = elegido else {
+            // Nadie ha llegado todavía: CPU ociosa hasta la próxima llegada
+            let llegada = proxima_llegada(procesos, reloj, |j| pendiente[j]).unwrap_or(reloj);
+            registrar(&mut gantt, PID_OCIOSO, reloj, llegada);
+            reloj = llegada;
+            continue;
+        };
+
+        pendiente[i] = false;
+        despachados += 1;
+        inicio[i] = reloj;
+        reloj += procesos[i].rafaga;
+        fin[i] = reloj;
+        registrar(&mut gantt, &procesos[i].pid, inicio[i], fin[i]);
+    }
+
+    let cambios_contexto = n.saturating_sub(1) as u32;
+    resumir(procesos, &inicio, &fin, gantt, Vec::new(), cambios_contexto)
+}
+
+// Entre los procesos que cumplen `candidato` y ya llegaron a `reloj`, el primero según
+// `comparar`; los empates se resuelven por llegada y luego por orden de entrada.
+fn primero_listo(
+    procesos: &[ProcesoEntrada],
+    reloj: f64,
+    candidato: impl Fn(usize) -> bool,
+    comparar: impl Fn(usize, usize) -> Ordering,
+) -> Option<usize> {
+    (0..procesos.len())
+        .filter(|&i| candidato(i) && procesos[i].llegada <= reloj + EPSILON)
+        .min_by(|&a, &b| {
+            comparar(a, b)
+                .then(procesos[a].llegada.total_cmp(&procesos[b].llegada))
+                .then(a.cmp(&b))
+        })
+}
+
+// Primera llegada posterior a `reloj` entre los procesos que cumplen `candidato`
+fn proxima_llegada(
+    procesos: &[ProcesoEntrada],
+    reloj: f64,
+    candidato: impl Fn(usize) -> bool,
+) -> Option<f64> {
+    (0..procesos.len())
+        .filter(|&i| candidato(i))
+        .map(|i| procesos[i].llegada)
+        .filter(|&t| t > reloj + EPSILON)
+        .reduce(f64::min)
+}
+
+// Índices de los procesos ordenados por llegada; sort_by es estable, así que los
+// empates conservan el orden de entrada.
+fn orden_llegada(procesos: &[ProcesoEntrada]) -> Vec<usize> {
+    let mut orden: Vec<usize> = (0..procesos.len()).collect();
+    orden.sort_by(|&a, &b| procesos[a].llegada.total_cmp(&procesos[b].llegada));
+    orden
+}
+
+// Encola los procesos (en orden de llegada) que ya llegaron al instante `reloj`
+fn admitir(
+    procesos: &[ProcesoEntrada],
+    orden: &[usize],
+    siguiente: &mut usize,
+    reloj: f64,
+    cola: &mut VecDeque<usize>,
+) {
+    while *siguiente < orden.len() && procesos[orden[*siguiente]].llegada <= reloj + EPSILON {
+        cola.push_back(orden[*siguiente]);
+        *siguiente += 1;
+    }
+}
+
+// Agrega un tramo al diagrama de Gantt, fusionándolo con el anterior si es del
+// mismo proceso y contiguo. Los tramos vacíos se descartan.
+fn registrar(gantt: &mut Vec<SegmentoGantt>, pid: &str, inicio: f64, fin: f64) {
+    if fin - inicio <= EPSILON {
+        return;
+    }
+    if let Some(ultimo) = gantt.last_mut() {
+        if ultimo.pid == pid && (ultimo.fin - inicio).abs() <= EPSILON {
+            ultimo.fin = fin;
+            return;
+        }
+    }
+    gantt.push(SegmentoGantt {
+        pid: pid.to_string(),
+        inicio,
+        fin,
+    });
+}
+
+// Arma el resultado por proceso (en el orden de entrada) y los promedios
+fn resumir(
+    procesos: &[ProcesoEntrada],
+    inicio: &[f64],
+    fin: &[f64],
+    gantt: Vec<SegmentoGantt>,
+    bloqueos: Vec<SegmentoGantt>,
+    cambios_contexto: u32,
+) -> ResultadoSimulacion {
+    let registros: Vec<ResultadoProceso> = procesos
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let retorno = fin[i] - p.llegada;
+            let rafaga = p.cpu_total();
+            ResultadoProceso {
+                pid: p.pid.clone(),
+                llegada: p.llegada,
+                rafaga,
+                prioridad: p.prioridad,
+                prioridad_envejecida: p.prioridad,
+                inicio: inicio[i],
+                fin: fin[i],
+                // Tiempo en la cola de listos: ni en CPU ni bloqueado
+                espera: (retorno - rafaga - p.io_total()).max(0.0),
+                retorno,
+                respuesta: (inicio[i] - p.llegada).max(0.0),
+                nivel_final: None,
+            }
+        })
+        .collect();
+
+    let (espera_promedio, espera_desviacion) = estadisticas(registros.iter().map(|r| r.espera));
+    let (retorno_promedio, retorno_desviacion) = estadisticas(registros.iter().map(|r| r.retorno));
+    let (respuesta_promedio, respuesta_desviacion) =
+        estadisticas(registros.iter().map(|r| r.respuesta));
+
+    // Ventana desde la primera llegada hasta el último fin: lo que no es CPU ocupada
+    // dentro de ella es tiempo ocioso (huecos sin procesos listos)
+    let primera_llegada = registros
+        .iter()
+        .map(|r| r.llegada)
+        .reduce(f64::min)
+        .unwrap_or(0.0);
+    let primer_inicio = registros
+        .iter()
+        .map(|r| r.inicio)
+        .reduce(f64::min)
+        .unwrap_or(0.0);
+    let ultimo_fin = registros
+        .iter()
+        .map(|r| r.fin)
+        .reduce(f64::max)
+        .unwrap_or(0.0);
+    let ventana = (ultimo_fin - primera_llegada).max(0.0);
+    let tiempo_cambio_contexto: f64 = gantt
+        .iter()
+        .filter(|s| s.pid == PID_CAMBIO_CONTEXTO)
+        .map(|s| s.fin - s.inicio)
+        .fold(0.0, |total, d| total + d);
+    // Los cambios de contexto no son trabajo útil, pero tampoco CPU ociosa. Se
+    // acumula con fold porque sum() de una lista vacía da -0.0.
+    let tiempo_ocupado: f64 = gantt
+        .iter()
+        .filter(|s| s.pid != PID_OCIOSO && s.pid != PID_CAMBIO_CONTEXTO)
+        .map(|s| s.fin - s.inicio)
+        .fold(0.0, |total, d| total + d);
+    let tiempo_ocioso = (ventana - tiempo_ocupado - tiempo_cambio_contexto).max(0.0);
+    let utilizacion = if ventana > 0.0 {
+        tiempo_ocupado / ventana * 100.0
+    } else {
+        0.0
+    };
+    let expropiaciones = contar_expropiaciones(&gantt, &bloqueos);
+
+    ResultadoSimulacion {
+        procesos: registros,
+        gantt,
+        bloqueos,
+        gantt_io: Vec::new(),
+        espera_promedio,
+        retorno_promedio,
+        respuesta_promedio,
+        espera_desviacion,
+        retorno_desviacion,
+        respuesta_desviacion,
+        cambios_contexto,
+        tiempo_cambio_contexto,
+        tiempo_ocupado,
+        tiempo_ocioso,
+        utilizacion,
+        expropiaciones,
+        primer_inicio,
+        ultimo_fin,
+        makespan: ventana,
+    }
+}
+
+// Veces que un proceso dejó la CPU sin haber terminado. Salir a hacer E/S no cuenta,
+// y como el Gantt fusiona tramos contiguos, tampoco un redespacho inmediato.
+fn contar_expropiaciones(gantt: &[SegmentoGantt], bloqueos: &[SegmentoGantt]) -> u32 {
+    let mut ultimo_tramo: HashMap<&str, usize> = HashMap::new();
+    for (i, s) in gantt.iter().enumerate() {
+        ultimo_tramo.insert(&s.pid, i);
+    }
+    gantt
+        .iter()
+        .enumerate()
+        .filter(|&(i, s)| {
+            s.pid != PID_OCIOSO
+                && s.pid != PID_CAMBIO_CONTEXTO
+                && ultimo_tramo[s.pid.as_str()] != i
+                && !bloqueos
+                    .iter()
+                    .any(|b| b.pid == s.pid && (b.inicio - s.fin).abs() <= EPSILON)
+        })
+        .count() as u32
+}
+
+// Promedio y desviación estándar poblacional; (0, 0) si no hay valores
+fn estadisticas(valores: impl Iterator<Item = f64> + Clone) -> (f64, f64) {
+    let n = valores.clone().count();
+    if n == 0 {
+        return (0.0, 0.0);
+    }
+    let n = n as f64;
+    let promedio = valores.clone().sum::<f64>() / n;
+    let varianza = valores.map(|v| (v - promedio).powi(2)).sum::<f64>() / n;
+    (promedio, varianza.sqrt())
+}
+
+// ----- Gantt en texto -----
+// Columnas por unidad de tiempo cuando la línea entra completa en el ancho pedido
+const COLUMNAS_POR_UNIDAD: f64 = 4.0;
+
+// Sin decimales si el instante es entero; si no, hasta dos
+fn formatear_instante(t: f64) -> String {
+    if (t - t.round()).abs() <= EPSILON {
+        format!("{}", t.round())
+    } else {
+        format!("{t:.2}")
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
+}
+
+impl ResultadoSimulacion {
+    // Línea de tiempo para logs y pruebas, p. ej.
+    //   | P1 | P2 |  idle  | P1 |
+    //   0    1    2        4    5
+    // Cada tramo ocupa un ancho proporcional a su duración; si la línea no entra en
+    // `max_columnas` se escala hacia abajo (con al menos una columna por tramo) y los
+    // nombres que no caben se recortan. Las marcas del eje que se pisarían se omiten.
+    pub fn a_ascii(&self, max_columnas: usize) -> String {
+        // Los huecos del Gantt se muestran como CPU ociosa
+        let mut tramos: Vec<(&str, f64, f64)> = Vec::with_capacity(self.gantt.len());
+        for s in &self.gantt {
+            if let Some(&(_, _, fin)) = tramos.last() {
+                if s.inicio - fin > EPSILON {
+                    tramos.push((PID_OCIOSO, fin, s.inicio));
+                }
+            }
+            tramos.push((&s.pid, s.inicio, s.fin));
+        }
+        let (Some(primero), Some(ultimo)) = (tramos.first(), tramos.last()) else {
+            return String::new();
+        };
+        let total = ultimo.2 - primero.1;
+
+        // Los separadores también ocupan columnas
+        let disponibles = max_columnas
+            .saturating_sub(tramos.len() + 1)
+            .max(tramos.len());
+        let escala = COLUMNAS_POR_UNIDAD.min(disponibles as f64 / total);
+
+        let mut barra = String::from("|");
+        let mut marcas = vec![(0, primero.1)];
+        for &(pid, inicio, fin) in &tramos {
+            let ancho = (((fin - inicio) * escala + EPSILON).floor() as usize).max(1);
+            let nombre: String = pid.chars().take(ancho).collect();
+            let relleno = ancho - nombre.chars().count();
+            let izquierda = relleno / 2;
+            barra.push_str(&" ".repeat(izquierda));
+            barra.push_str(&nombre);
+            barra.push_str(&" ".repeat(relleno - izquierda));
+            barra.push('|');
+            marcas.push((barra.chars().count() - 1, fin));
+        }
+
+        let mut eje = String::new();
+        for (columna, t) in marcas {
+            let columnas_usadas = eje.chars().count();
+            // Se deja al menos un espacio entre marcas
+            if columnas_usadas > 0 && columna <= columnas_usadas {
+                continue;
+            }
+            eje.push_str(&" ".repeat(columna - columnas_usadas));
+            eje.push_str(&formatear_instante(t));
+        }
+        format!("{barra}\n{eje}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entrada(pid: &str, llegada: f64, rafaga: f64) -> ProcesoEntrada {
+        ProcesoEntrada {
+            pid: pid.to_string(),
+            llegada,
+            rafaga,
+            prioridad: 0,
+            rafagas: Vec::new(),
+        }
+    }
+
+    fn tramos(resultado: &ResultadoSimulacion) -> Vec<(&str, f64, f64)> {
+        resultado
+            .gantt
+            .iter()
+            .map(|s| (s.pid.as_str(), s.inicio, s.fin))
+            .collect()
+    }
+
+    fn rechaza(procesos: &[ProcesoEntrada]) -> bool {
+        matches!(validar_entrada(procesos), Err(ErrorSim::EntradaInvalida(_)))
+    }
+
+    #[test]
+    fn mlfq_baja_de_nivel_al_agotar_el_quantum() {
+        let procesos = vec![entrada("A", 0.0, 10.0), entrada("B", 0.0, 1.0)];
+        let r = mlfq(&procesos, &[2.0, 4.0], None, 0.0);
+
+        assert_eq!(
+            tramos(&r),
+            vec![("A", 0.0, 2.0), ("B", 2.0, 3.0), ("A", 3.0, 11.0)]
+        );
+        // A agotó el quantum de la cola 0; B terminó antes y se quedó arriba
+        assert_eq!(r.procesos[0].nivel_final, Some(1));
+        assert_eq!(r.procesos[1].nivel_final, Some(0));
+    }
+
+    #[test]
+    fn mlfq_llegada_a_mitad_de_quantum_desaloja_a_cola_inferior() {
+        // A ya está en la cola 1 (quantum 8) cuando B llega en t=5
+        let procesos = vec![entrada("A", 0.0, 12.0), entrada("B", 5.0, 2.0)];
+        let r = mlfq(&procesos, &[2.0, 8.0], None, 0.0);
+
+        // A vuelve al frente de la cola 1 y retoma los 5 ticks que le quedaban del
+        // quantum; al agotarlo sigue en la cola 1, que es la última.
+        assert_eq!(
+            tramos(&r),
+            vec![("A", 0.0, 5.0), ("B", 5.0, 7.0), ("A", 7.0, 14.0)]
+        );
+        assert_eq!(r.procesos[0].nivel_final, Some(1));
+        assert_eq!(r.procesos[1].nivel_final, Some(0));
+        assert_eq!(r.cambios_contexto, 2);
+    }
+
+    #[test]
+    fn mlfq_llegada_no_interrumpe_a_la_cola_superior() {
+        let procesos = vec![entrada("A", 0.0, 4.0), entrada("B", 1.0, 1.0)];
+        let r = mlfq(&procesos, &[3.0, 6.0], None, 0.0);
+
+        assert_eq!(
+            tramos(&r),
+            vec![("A", 0.0, 3.0), ("B", 3.0, 4.0), ("A", 4.0, 5.0)]
+        );
+    }
+
+    #[test]
+    fn mlfq_boost_devuelve_todo_a_la_cola_superior() {
+        let procesos = vec![entrada("A", 0.0, 6.0), entrada("B", 1.0, 6.0)];
+
+        let sin_boost = mlfq(&procesos, &[2.0, 10.0], None, 0.0);
+        assert_eq!(
+            tramos(&sin_boost),
+            vec![
+                ("A", 0.0, 2.0),
+                ("B", 2.0, 4.0),
+                ("A", 4.0, 8.0),
+                ("B", 8.0, 12.0)
+            ]
+        );
+
+        // Con boost cada 4 ticks ambos vuelven a la cola 0 y alternan con quantum 2
+        let con_boost = mlfq(&procesos, &[2.0, 10.0], Some(4.0), 0.0);
+        assert_eq!(
+            tramos(&con_boost),
+            vec![
+                ("A", 0.0, 2.0),
+                ("B", 2.0, 4.0),
+                ("A", 4.0, 6.0),
+                ("B", 6.0, 8.0),
+                ("A", 8.0, 10.0),
+                ("B", 10.0, 12.0),
+            ]
+        );
+        assert_eq!(con_boost.procesos[0].nivel_final, Some(0));
+        assert_eq!(con_boost.procesos[1].nivel_final, Some(0));
+    }
+
+    #[test]
+    fn round_robin_con_rafaga_fraccionaria() {
+        // Con un único proceso el Gantt fusionaría los tramos contiguos; B intercala
+        // sus quantums para que se vean las tres porciones de A.
+        let procesos = vec![entrada("A", 0.0, 2.5), entrada("B", 0.0, 2.0)];
+        let r = round_robin(&procesos, 1.0, 0.0);
+
+        let porciones: Vec<(f64, f64)> = tramos(&r)
+            .into_iter()
+            .filter(|&(pid, _, _)| pid == "A")
+            .map(|(_, inicio, fin)| (inicio, fin))
+            .collect();
+        assert_eq!(porciones, vec![(0.0, 1.0), (2.0, 3.0), (4.0, 4.5)]);
+        assert_eq!(r.procesos[0].fin, 4.5);
+        assert_eq!(r.procesos[0].espera, 2.0);
+        assert_eq!(r.procesos[1].fin, 4.0);
+    }
+
+    #[test]
+    fn el_criterio_de_desempate_decide_quien_empieza() {
+        let mut a = entrada("A", 0.0, 2.0);
+        a.prioridad = 5;
+        let mut b = entrada("B", 0.0, 5.0);
+        b.prioridad = 1;
+        let procesos = vec![a, b];
+        let primero = |r: &ResultadoSimulacion| r.gantt[0].pid.clone();
+
+        // Llegadas simultáneas: FIFO respeta el orden de entrada, la variante por
+        // prioridad elige a B
+        assert_eq!(primero(&fifo(&procesos)), "A");
+        assert_eq!(primero(&fifo_prioridad(&procesos)), "B");
+        // SJF elige la ráfaga corta y LJF la larga
+        assert_eq!(primero(&sjf(&procesos)), "A");
+        assert_eq!(primero(&ljf(&procesos)), "B");
+        assert_eq!(
+            tramos(&ljf(&procesos)),
+            vec![("B", 0.0, 5.0), ("A", 5.0, 7.0)]
+        );
+    }
+
+    #[test]
+    fn makespan_descuenta_el_ocio_inicial() {
+        // Nadie llega hasta t=3: el Gantt empieza con CPU ociosa, pero el makespan se
+        // mide desde la primera llegada
+        let procesos = vec![entrada("A", 3.0, 2.0), entrada("B", 4.0, 1.0)];
+        let r = fifo(&procesos);
+
+        assert_eq!(r.gantt[0].pid, PID_OCIOSO);
+        assert_eq!(r.primer_inicio, 3.0);
+        assert_eq!(r.ultimo_fin, 6.0);
+        assert_eq!(r.makespan, 3.0);
+    }
+
+    #[test]
+    fn caso_convoy_penaliza_a_fifo() {
+        let procesos = caso_convoy();
+        let espera =
+            |r: &ResultadoSimulacion| -> Vec<f64> { r.procesos.iter().map(|p| p.espera).collect() };
+
+        let r_fifo = fifo(&procesos);
+        assert_eq!(espera(&r_fifo), vec![0.0, 24.0, 27.0, 30.0, 32.0]);
+        assert!((r_fifo.espera_promedio - 22.6).abs() < 1e-9);
+
+        let r_sjf = sjf(&procesos);
+        assert_eq!(espera(&r_sjf), vec![9.0, 3.0, 6.0, 1.0, 0.0]);
+        assert!((r_sjf.espera_promedio - 3.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn distribucion_reparte_en_intervalos_iguales() {
+        let procesos: Vec<ProcesoEntrada> = [1.0, 2.0, 3.0, 4.0, 10.0]
+            .into_iter()
+            .enumerate()
+            .map(|(i, r)| entrada(&format!("P{i}"), 0.0, r))
+            .collect();
+        assert_eq!(
+            distribucion_bursts(&procesos, 3),
+            vec![(1.0, 4.0, 3), (4.0, 7.0, 1), (7.0, 10.0, 1)]
+        );
+    }
+
+    #[test]
+    fn distribucion_con_rafagas_iguales_es_un_solo_intervalo() {
+        let procesos = vec![entrada("A", 0.0, 5.0), entrada("B", 2.0, 5.0)];
+        assert_eq!(distribucion_bursts(&procesos, 4), vec![(5.0, 5.0, 2)]);
+        assert!(distribucion_bursts(&[], 4).is_empty());
+    }
+
+    #[test]
+    fn gantt_ascii_con_tramos_proporcionales() {
+        let procesos = vec![entrada("A", 0.0, 2.0), entrada("B", 4.0, 1.0)];
+        assert_eq!(
+            fifo(&procesos).a_ascii(80),
+            "|   A    |  idle  | B  |\n0        2        4    5"
+        );
+        // Con poco ancho se escala y los nombres se recortan
+        assert_eq!(fifo(&procesos).a_ascii(8), "|A|i|B|\n0 2 4 5");
+    }
+
+    #[test]
+    fn dispositivo_unico_encola_las_e_s_solapadas() {
+        let con_io = |pid: &str, io: f64| ProcesoEntrada {
+            rafagas: vec![Rafaga::Cpu(2.0), Rafaga::Io(io), Rafaga::Cpu(1.0)],
+            ..entrada(pid, 0.0, 0.0)
+        };
+        let procesos = vec![con_io("A", 3.0), con_io("B", 2.0)];
+        let r = con_dispositivo_io(&procesos, None, 0.0);
+
+        // B pide E/S en 4 pero el dispositivo sigue con A hasta 5
+        let io: Vec<_> = r
+            .gantt_io
+            .iter()
+            .map(|s| (s.pid.as_str(), s.inicio, s.fin))
+            .collect();
+        assert_eq!(io, vec![("A", 2.0, 5.0), ("B", 5.0, 7.0)]);
+        assert_eq!(
+            tramos(&r),
+            vec![
+                ("A", 0.0, 2.0),
+                ("B", 2.0, 4.0),
+                (PID_OCIOSO, 4.0, 5.0),
+                ("A", 5.0, 6.0),
+                (PID_OCIOSO, 6.0, 7.0),
+                ("B", 7.0, 8.0),
+            ]
+        );
+        // La cola del dispositivo cuenta como bloqueo, no como espera
+        assert_eq!(r.procesos[1].espera, 2.0);
+        // Con E/S en paralelo B vuelve en 6 y no hay cola
+        assert!(fifo(&procesos).gantt_io.is_empty());
+        assert_eq!(fifo(&procesos).procesos[1].fin, 7.0);
+    }
+
+    #[test]
+    fn procesos_demo_es_reproducible() {
+        let resumen = |v: Vec<ProcesoEntrada>| -> Vec<(String, f64, f64, i32)> {
+            v.into_iter()
+                .map(|p| (p.pid, p.llegada, p.rafaga, p.prioridad))
+                .collect()
+        };
+        let a = resumen(procesos_demo(20, 42));
+        assert_eq!(a, resumen(procesos_demo(20, 42)));
+        assert_ne!(a, resumen(procesos_demo(20, 43)));
+        assert!(a
+            .iter()
+            .all(|(_, llegada, rafaga, _)| *llegada <= 40.0 && (1.0..=10.0).contains(rafaga)));
+    }
+
+    #[test]
+    fn fifo_rellena_el_hueco_hasta_la_siguiente_llegada() {
+        let procesos = vec![
+            entrada("A", 0.0, 2.0),
+            entrada("B", 5.0, 1.0),
+            entrada("C", 5.0, 1.0),
+        ];
+        assert_eq!(
+            tramos(&fifo(&procesos)),
+            vec![
+                ("A", 0.0, 2.0),
+                (PID_OCIOSO, 2.0, 5.0),
+                ("B", 5.0, 6.0),
+                ("C", 6.0, 7.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn round_robin_atiende_al_que_llega_durante_el_quantum() {
+        let procesos = vec![entrada("A", 0.0, 3.0), entrada("B", 2.0, 1.0)];
+        assert_eq!(
+            tramos(&round_robin(&procesos, 2.0, 0.0)),
+            vec![("A", 0.0, 2.0), ("B", 2.0, 3.0), ("A", 3.0, 4.0)]
+        );
+    }
+
+    #[test]
+    fn fifo_calcula_las_metricas_de_cada_proceso() {
+        // A 0-3, B 3-5, C 5-6
+        let procesos = vec![
+            entrada("A", 0.0, 3.0),
+            entrada("B", 1.0, 2.0),
+            entrada("C", 4.0, 1.0),
+        ];
+        let r = fifo(&procesos);
+        let metricas: Vec<(f64, f64, f64)> = r
+            .procesos
+            .iter()
+            .map(|p| (p.espera, p.retorno, p.respuesta))
+            .collect();
+
+        assert_eq!(
+            metricas,
+            vec![(0.0, 3.0, 0.0), (2.0, 4.0, 2.0), (1.0, 2.0, 1.0)]
+        );
+        assert_eq!(r.espera_promedio, 1.0);
+        assert_eq!(r.retorno_promedio, 3.0);
+        assert_eq!(r.respuesta_promedio, 1.0);
+    }
+
+    #[test]
+    fn srtf_no_desaloja_ante_un_empate() {
+        // En t=1 A y B tienen 3 restantes: A sigue y C, que llega después, espera a B
+        let procesos = vec![
+            entrada("A", 0.0, 4.0),
+            entrada("B", 1.0, 3.0),
+            entrada("C", 2.0, 3.0),
+        ];
+        let r = srtf(&procesos, 0.0);
+
+        assert_eq!(
+            tramos(&r),
+            vec![("A", 0.0, 4.0), ("B", 4.0, 7.0), ("C", 7.0, 10.0)]
+        );
+        assert_eq!(r.cambios_contexto, 2);
+    }
+
+    #[test]
+    fn prioridad_expropiativa_desaloja_al_llegar_uno_mejor() {
+        let mut a = entrada("A", 0.0, 5.0);
+        a.prioridad = 3;
+        let mut b = entrada("B", 1.0, 2.0);
+        b.prioridad = 1;
+        let r = prioridad(&[a, b], true, None, 0.0);
+
+        assert_eq!(
+            tramos(&r),
+            vec![("A", 0.0, 1.0), ("B", 1.0, 3.0), ("A", 3.0, 7.0)]
+        );
+    }
+
+    #[test]
+    fn envejecimiento_rescata_al_proceso_postergado() {
+        let con_prioridad = |pid: &str, llegada: f64, rafaga: f64, prioridad: i32| ProcesoEntrada {
+            prioridad,
+            ..entrada(pid, llegada, rafaga)
+        };
+        // B espera a A 10 ticks; sin envejecimiento C, que llega después con mejor
+        // prioridad, se le adelanta
+        let procesos = vec![
+            con_prioridad("A", 0.0, 10.0, 1),
+            con_prioridad("B", 0.0, 3.0, 5),
+            con_prioridad("C", 5.0, 3.0, 2),
+        ];
+
+        let sin = prioridad(&procesos, false, None, 0.0);
+        assert_eq!(sin.gantt[1].pid, "C");
+        assert_eq!(sin.procesos[1].prioridad_envejecida, 5);
+
+        let con = prioridad(&procesos, false, Some(2.0), 0.0);
+        assert_eq!(con.gantt[1].pid, "B");
+        assert!(con.procesos[1].prioridad_envejecida < con.procesos[1].prioridad);
+    }
+
+    #[test]
+    fn round_robin_cobra_cada_cambio_de_contexto() {
+        let procesos = vec![entrada("A", 0.0, 3.0), entrada("B", 0.0, 3.0)];
+        let r = round_robin(&procesos, 1.0, 0.5);
+
+        let cambios = r
+            .gantt
+            .iter()
+            .filter(|s| s.pid == PID_CAMBIO_CONTEXTO)
+            .count();
+        assert_eq!(cambios, 5);
+        assert_eq!(r.tiempo_cambio_contexto, 2.5);
+        assert_eq!(r.ultimo_fin, 8.5);
+
+        // Sin costo no hay tramos de cambio y el total es 0 (no -0.0)
+        let sin_costo = round_robin(&procesos, 1.0, 0.0);
+        assert!(sin_costo.tiempo_cambio_contexto.is_sign_positive());
+    }
+
+    #[test]
+    fn round_robin_cede_la_cpu_durante_la_e_s() {
+        let a = ProcesoEntrada {
+            rafagas: vec![Rafaga::Cpu(3.0), Rafaga::Io(3.0), Rafaga::Cpu(1.0)],
+            ..entrada("A", 0.0, 0.0)
+        };
+        let procesos = vec![a, entrada("B", 0.0, 3.0)];
+        let r = round_robin(&procesos, 3.0, 0.0);
+
+        // A queda bloqueado de 3 a 6 mientras B usa la CPU
+        assert_eq!(
+            tramos(&r),
+            vec![("A", 0.0, 3.0), ("B", 3.0, 6.0), ("A", 6.0, 7.0)]
+        );
+        let bloqueos: Vec<_> = r
+            .bloqueos
+            .iter()
+            .map(|s| (s.pid.as_str(), s.inicio, s.fin))
+            .collect();
+        assert_eq!(bloqueos, vec![("A", 3.0, 6.0)]);
+        // El bloqueo no cuenta como espera
+        assert_eq!(r.procesos[0].espera, 0.0);
+    }
+
+    #[test]
+    fn validar_entrada_rechaza_valores_sin_sentido() {
+        assert!(validar_entrada(&[entrada("A", 0.0, 2.0), entrada("B", 1.0, 3.0)]).is_ok());
+
+        assert!(rechaza(&[entrada("A", 0.0, f64::NAN)]));
+        assert!(rechaza(&[entrada("A", f64::NAN, 1.0)]));
+        assert!(rechaza(&[entrada("A", -1.0, 1.0)]));
+        assert!(rechaza(&[entrada("A", 0.0, -2.0)]));
+        assert!(rechaza(&[entrada("A", 0.0, 1.0), entrada("A", 2.0, 1.0)]));
+        // Con ráfagas explícitas se exige al menos una de CPU
+        assert!(rechaza(&[ProcesoEntrada {
+            rafagas: vec![Rafaga::Io(2.0)],
+            ..entrada("A", 0.0, 0.0)
+        }]));
+    }
+}
@@ -0,0 +1,453 @@
+// ----- Consultas al sistema operativo que sysinfo no expone -----
+use std::io;
+
+// Campos de /proc/<pid>/stat a partir del estado (campo 3). El nombre (campo 2) va
+// entre paréntesis y puede contener espacios, por eso se corta en el último ')'.
+#[cfg(target_os = "linux")]
+fn campos_stat(pid: u32) -> Option<Vec<String>> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    let resto = &stat[stat.rfind(')')? + 1..];
+    Some(resto.split_whitespace().map(str::to_string).collect())
+}
+
+// Valor nice del proceso (-20 a 19, menor = más prioritario)
+#[cfg(target_os = "linux")]
+pub fn prioridad(pid: u32) -> Option<i32> {
+    // nice es el campo 19 (índice 18 contando desde 0)
+    campos_stat(pid)?.get(19 - 3)?.parse().ok()
+}
+
+// En Windows se traduce la clase de prioridad a la escala nice equivalente
+#[cfg(windows)]
+pub fn prioridad(pid: u32) -> Option<i32> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        GetPriorityClass, OpenProcess, ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS,
+        HIGH_PRIORITY_CLASS, IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS,
+        PROCESS_QUERY_LIMITED_INFORMATION, REALTIME_PRIORITY_CLASS,
+    };
+
+    let clase = unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return None;
+        }
+        let clase = GetPriorityClass(handle);
+        CloseHandle(handle);
+        clase
+    };
+
+    match clase {
+        IDLE_PRIORITY_CLASS => Some(19),
+        BELOW_NORMAL_PRIORITY_CLASS => Some(10),
+        NORMAL_PRIORITY_CLASS => Some(0),
+        ABOVE_NORMAL_PRIORITY_CLASS => Some(-5),
+        HIGH_PRIORITY_CLASS => Some(-10),
+        REALTIME_PRIORITY_CLASS => Some(-20),
+        _ => None,
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn prioridad(_pid: u32) -> Option<i32> {
+    None
+}
+
+// Sueño no interrumpible: el estado (campo 3) es 'D'
+#[cfg(target_os = "linux")]
+pub fn en_espera_io(pid: u32) -> bool {
+    campos_stat(pid).is_some_and(|campos| campos.first().is_some_and(|e| e == "D"))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn en_espera_io(_pid: u32) -> bool {
+    false
+}
+
+// Número de hilos del proceso: cada hilo tiene una entrada en /proc/<pid>/task
+#[cfg(target_os = "linux")]
+pub fn hilos(pid: u32) -> Option<u32> {
+    let entradas = std::fs::read_dir(format!("/proc/{pid}/task")).ok()?;
+    Some(entradas.count() as u32)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn hilos(_pid: u32) -> Option<u32> {
+    None
+}
+
+// Memoria del proceso en swap, en bytes. sysinfo no la expone por proceso, pero
+// /proc/<pid>/status la informa en la línea "VmSwap:    1234 kB".
+#[cfg(target_os = "linux")]
+pub fn swap(pid: u32) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    let linea = status.lines().find(|l| l.starts_with("VmSwap:"))?;
+    let kb: u64 = linea.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn swap(_pid: u32) -> Option<u64> {
+    None
+}
+
+// ----- Privilegios del propio proceso -----
+// Con euid 0 se puede señalar y repriorizar procesos de cualquier usuario
+#[cfg(unix)]
+pub fn privilegios_elevados() -> bool {
+    unsafe { libc::geteuid() == 0 }
+}
+
+// Token elevado: la app se abrió "como administrador"
+#[cfg(windows)]
+pub fn privilegios_elevados() -> bool {
+    use windows_sys::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows_sys::Win32::Security::{
+        GetTokenInformation, TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY,
+    };
+    use windows_sys::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+    unsafe {
+        let mut token: HANDLE = std::ptr::null_mut();
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0 {
+            return false;
+        }
+        let mut elevacion: TOKEN_ELEVATION = std::mem::zeroed();
+        let mut tam = 0u32;
+        let ok = GetTokenInformation(
+            token,
+            TokenElevation,
+            &mut elevacion as *mut TOKEN_ELEVATION as *mut _,
+            std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+            &mut tam,
+        );
+        CloseHandle(token);
+        ok != 0 && elevacion.TokenIsElevated != 0
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn privilegios_elevados() -> bool {
+    false
+}
+
+// ----- Ventana en primer plano -----
+#[cfg(windows)]
+pub fn proceso_primer_plano() -> Option<u32> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowThreadProcessId,
+    };
+
+    let mut pid = 0u32;
+    unsafe {
+        let ventana = GetForegroundWindow();
+        if ventana.is_null() || GetWindowThreadProcessId(ventana, &mut pid) == 0 {
+            return None;
+        }
+    }
+    (pid != 0).then_some(pid)
+}
+
+// En X11 se consulta al gestor de ventanas con xprop (_NET_ACTIVE_WINDOW y luego
+// _NET_WM_PID de esa ventana). Wayland no permite a un cliente conocer la ventana
+// activa, y sin xprop o sin un gestor compatible no hay respuesta.
+#[cfg(target_os = "linux")]
+pub fn proceso_primer_plano() -> Option<u32> {
+    use std::process::Command;
+
+    // Sin servidor X no hay a quién preguntar
+    std::env::var_os("DISPLAY")?;
+    // Salidas de la forma "_NET_ACTIVE_WINDOW(WINDOW): window id # 0x3a00007" y
+    // "_NET_WM_PID(CARDINAL) = 4321": interesa el último campo
+    let consultar = |args: &[&str]| -> Option<String> {
+        let salida = Command::new("xprop").args(args).output().ok()?;
+        if !salida.status.success() {
+            return None;
+        }
+        let texto = String::from_utf8(salida.stdout).ok()?;
+        texto.split_whitespace().last().map(str::to_string)
+    };
+    let ventana = consultar(&["-root", "_NET_ACTIVE_WINDOW"])?;
+    if !ventana.starts_with("0x") || ventana == "0x0" {
+        return None;
+    }
+    consultar(&["-id", &ventana, "_NET_WM_PID"])?.parse().ok()
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn proceso_primer_plano() -> Option<u32> {
+    None
+}
+
+// ----- Suspensión y reanudación -----
+#[cfg(unix)]
+fn enviar_senal(pid: u32, senal: libc::c_int) -> io::Result<()> {
+    if unsafe { libc::kill(pid as libc::pid_t, senal) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(unix)]
+pub fn suspender(pid: u32) -> io::Result<()> {
+    enviar_senal(pid, libc::SIGSTOP)
+}
+
+#[cfg(unix)]
+pub fn reanudar(pid: u32) -> io::Result<()> {
+    enviar_senal(pid, libc::SIGCONT)
+}
+
+// Windows no tiene señales: se suspende/reanuda cada hilo del proceso
+#[cfg(windows)]
+fn aplicar_a_hilos(
+    pid: u32,
+    accion: unsafe extern "system" fn(windows_sys::Win32::Foundation::HANDLE) -> u32,
+) -> io::Result<()> {
+    use windows_sys::Win32::Foundation::{CloseHandle, INVALID_HANDLE_VALUE};
+    use windows_sys::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32,
+    };
+    use windows_sys::Win32::System::Threading::{OpenThread, THREAD_SUSPEND_RESUME};
+
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut entrada: THREADENTRY32 = std::mem::zeroed();
+        entrada.dwSize = std::mem::size_of::<THREADENTRY32>() as u32;
+        let mut resultado = Ok(());
+
+        let mut hay_hilo = Thread32First(snapshot, &mut entrada) != 0;
+        while hay_hilo {
+            if entrada.th32OwnerProcessID == pid {
+                let hilo = OpenThread(THREAD_SUSPEND_RESUME, 0, entrada.th32ThreadID);
+                if hilo.is_null() {
+                    resultado = Err(io::Error::last_os_error());
+                } else {
+                    if accion(hilo) == u32::MAX {
+                        resultado = Err(io::Error::last_os_error());
+                    }
+                    CloseHandle(hilo);
+                }
+            }
+            hay_hilo = Thread32Next(snapshot, &mut entrada) != 0;
+        }
+
+        CloseHandle(snapshot);
+        resultado
+    }
+}
+
+#[cfg(windows)]
+pub fn suspender(pid: u32) -> io::Result<()> {
+    aplicar_a_hilos(pid, windows_sys::Win32::System::Threading::SuspendThread)
+}
+
+#[cfg(windows)]
+pub fn reanudar(pid: u32) -> io::Result<()> {
+    aplicar_a_hilos(pid, windows_sys::Win32::System::Threading::ResumeThread)
+}
+
+// ----- Cambio de prioridad -----
+#[cfg(unix)]
+pub fn establecer_prioridad(pid: u32, nice: i32) -> io::Result<()> {
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+// Windows solo tiene clases de prioridad: se elige la más cercana al valor nice,
+// con los mismos puntos que usa prioridad() para leerla
+#[cfg(windows)]
+pub fn establecer_prioridad(pid: u32, nice: i32) -> io::Result<()> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess, SetPriorityClass, ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS,
+        HIGH_PRIORITY_CLASS, IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, PROCESS_SET_INFORMATION,
+        REALTIME_PRIORITY_CLASS,
+    };
+
+    let clase = match nice {
+        15.. => IDLE_PRIORITY_CLASS,
+        5..=14 => BELOW_NORMAL_PRIORITY_CLASS,
+        -2..=4 => NORMAL_PRIORITY_CLASS,
+        -7..=-3 => ABOVE_NORMAL_PRIORITY_CLASS,
+        -15..=-8 => HIGH_PRIORITY_CLASS,
+        _ => REALTIME_PRIORITY_CLASS,
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_SET_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        let resultado = if SetPriorityClass(handle, clase) != 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        };
+        CloseHandle(handle);
+        resultado
+    }
+}
+
+// ----- Afinidad de CPU -----
+// Índices de los núcleos en los que el proceso puede ejecutarse
+#[cfg(target_os = "linux")]
+pub fn afinidad(pid: u32) -> Option<Vec<usize>> {
+    let conjunto = unsafe {
+        let mut conjunto: libc::cpu_set_t = std::mem::zeroed();
+        let tam = std::mem::size_of::<libc::cpu_set_t>();
+        if libc::sched_getaffinity(pid as libc::pid_t, tam, &mut conjunto) != 0 {
+            return None;
+        }
+        conjunto
+    };
+    Some(
+        (0..libc::CPU_SETSIZE as usize)
+            .filter(|&nucleo| unsafe { libc::CPU_ISSET(nucleo, &conjunto) })
+            .collect(),
+    )
+}
+
+// Los índices deben ser menores que CPU_SETSIZE; el comando ya los valida contra la
+// cantidad de núcleos del equipo
+#[cfg(target_os = "linux")]
+pub fn establecer_afinidad(pid: u32, nucleos: &[usize]) -> io::Result<()> {
+    unsafe {
+        let mut conjunto: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_ZERO(&mut conjunto);
+        for &nucleo in nucleos {
+            libc::CPU_SET(nucleo, &mut conjunto);
+        }
+        let tam = std::mem::size_of::<libc::cpu_set_t>();
+        if libc::sched_setaffinity(pid as libc::pid_t, tam, &conjunto) == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+}
+
+// En Windows la afinidad es una máscara de bits del tamaño de un puntero
+#[cfg(windows)]
+pub fn afinidad(pid: u32) -> Option<Vec<usize>> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        GetProcessAffinityMask, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    let mascara = unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return None;
+        }
+        let mut del_proceso = 0usize;
+        let mut del_sistema = 0usize;
+        let ok = GetProcessAffinityMask(handle, &mut del_proceso, &mut del_sistema);
+        CloseHandle(handle);
+        if ok == 0 {
+            return None;
+        }
+        del_proceso
+    };
+    Some(
+        (0..usize::BITS as usize)
+            .filter(|&nucleo| mascara >> nucleo & 1 == 1)
+            .collect(),
+    )
+}
+
+#[cfg(windows)]
+pub fn establecer_afinidad(pid: u32, nucleos: &[usize]) -> io::Result<()> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess, SetProcessAffinityMask, PROCESS_SET_INFORMATION,
+    };
+
+    let mascara = nucleos.iter().fold(0usize, |m, &nucleo| m | 1 << nucleo);
+    unsafe {
+        let handle = OpenProcess(PROCESS_SET_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        let resultado = if SetProcessAffinityMask(handle, mascara) != 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        };
+        CloseHandle(handle);
+        resultado
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn afinidad(_pid: u32) -> Option<Vec<usize>> {
+    None
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn establecer_afinidad(_pid: u32, _nucleos: &[usize]) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "la plataforma no permite fijar la afinidad",
+    ))
+}
+
+// ----- Descriptores abiertos -----
+// Cantidad de descriptores (handles en Windows) y, donde se puede distinguir, cuántos
+// son sockets. Sin permisos sobre el proceso falla con PermissionDenied.
+#[cfg(target_os = "linux")]
+pub fn descriptores(pid: u32) -> io::Result<(u32, Option<u32>)> {
+    let mut total = 0;
+    let mut sockets = 0;
+    for entrada in std::fs::read_dir(format!("/proc/{pid}/fd"))? {
+        // Un descriptor cerrado entre la lectura del directorio y la del enlace
+        // simplemente no cuenta
+        let Ok(entrada) = entrada else { continue };
+        total += 1;
+        let destino = std::fs::read_link(entrada.path()).unwrap_or_default();
+        if destino.to_string_lossy().starts_with("socket:") {
+            sockets += 1;
+        }
+    }
+    Ok((total, Some(sockets)))
+}
+
+// Windows solo informa el total de handles, sin distinguir sockets
+#[cfg(windows)]
+pub fn descriptores(pid: u32) -> io::Result<(u32, Option<u32>)> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        GetProcessHandleCount, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        let mut total = 0u32;
+        let ok = GetProcessHandleCount(handle, &mut total);
+        let error = io::Error::last_os_error();
+        CloseHandle(handle);
+        if ok == 0 {
+            return Err(error);
+        }
+        Ok((total, None))
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn descriptores(_pid: u32) -> io::Result<(u32, Option<u32>)> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "la plataforma no permite contar los descriptores",
+    ))
+}
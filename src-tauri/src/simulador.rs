@@ -0,0 +1,263 @@
+// ----- Simulación paso a paso -----
+// A diferencia de planificador.rs, que resuelve la simulación completa de una vez,
+// aquí el estado se conserva entre llamadas y avanza de a un tick para poder
+// inspeccionar la cola de listos. Cada tick dura una unidad de tiempo, salvo que
+// antes termine la ráfaga, llegue un proceso o se agote el quantum de Round Robin:
+// entonces se corta ahí. No modela E/S: crear_simulacion rechaza las entradas con
+// ráfagas de E/S, así que cada proceso trae una única ráfaga de CPU.
+use crate::planificador::{quantum_valido, Algoritmo, ProcesoEntrada, EPSILON};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+#[derive(Serialize)]
+pub struct RestanteProceso {
+    pub pid: String,
+    pub restante: f64,
+}
+
+#[derive(Serialize)]
+pub struct EstadoSimulacion {
+    pub reloj: f64,
+    // Proceso que ocupó la CPU durante el último tick (None si estuvo ociosa)
+    pub ejecutando: Option<String>,
+    // Cola de listos en orden de ingreso; en SJF, SRTF y prioridad el próximo
+    // despachado se elige por clave, no por posición
+    pub cola: Vec<String>,
+    pub restantes: Vec<RestanteProceso>,
+    pub terminada: bool,
+}
+
+pub struct Simulador {
+    procesos: Vec<ProcesoEntrada>,
+    algoritmo: Algoritmo,
+    quantum: f64,
+    orden: Vec<usize>,
+    siguiente: usize,
+    restante: Vec<f64>,
+    cola: VecDeque<usize>,
+    actual: Option<usize>,
+    // Tiempo consumido del quantum en curso (solo Round Robin)
+    usado: f64,
+    reloj: f64,
+    ultimo_tick: Option<usize>,
+}
+
+impl Simulador {
+    // `quantum` solo lo usa Round Robin
+    pub fn nuevo(procesos: Vec<ProcesoEntrada>, algoritmo: Algoritmo, quantum: f64) -> Self {
+        let restante = procesos.iter().map(ProcesoEntrada::cpu_total).collect();
+        // Mismo desempate que el planificador: por llegada y luego por orden de entrada
+        let mut orden: Vec<usize> = (0..procesos.len()).collect();
+        orden.sort_by(|&a, &b| procesos[a].llegada.total_cmp(&procesos[b].llegada));
+        let mut simulador = Simulador {
+            procesos,
+            algoritmo,
+            quantum: quantum_valido(quantum),
+            orden,
+            siguiente: 0,
+            restante,
+            cola: VecDeque::new(),
+            actual: None,
+            usado: 0.0,
+            reloj: 0.0,
+            ultimo_tick: None,
+        };
+        simulador.admitir();
+        simulador
+    }
+
+    fn terminada(&self) -> bool {
+        self.actual.is_none() && self.cola.is_empty() && self.siguiente == self.orden.len()
+    }
+
+    fn admitir(&mut self) {
+        while self.siguiente < self.orden.len()
+            && self.procesos[self.orden[self.siguiente]].llegada <= self.reloj + EPSILON
+        {
+            let i = self.orden[self.siguiente];
+            self.siguiente += 1;
+            // Un proceso sin CPU termina al llegar
+            if self.restante[i] > EPSILON {
+                self.cola.push_back(i);
+            }
+        }
+    }
+
+    // Posición en la cola del primer proceso según `comparar` (empates por llegada y
+    // entrada)
+    fn posicion_min(&self, comparar: impl Fn(usize, usize) -> Ordering) -> Option<usize> {
+        (0..self.cola.len()).min_by(|&a, &b| {
+            let (i, j) = (self.cola[a], self.cola[b]);
+            comparar(i, j)
+                .then(
+                    self.procesos[i]
+                        .llegada
+                        .total_cmp(&self.procesos[j].llegada),
+                )
+                .then(i.cmp(&j))
+        })
+    }
+
+    // Decide quién ocupa la CPU en el tick que empieza en `reloj`
+    fn despachar(&mut self) {
+        let cpu = |i: usize| self.procesos[i].cpu_total();
+        let prioridad = |i: usize| self.procesos[i].prioridad;
+        let elegido = match self.algoritmo {
+            Algoritmo::Fifo => (self.actual.is_none() && !self.cola.is_empty()).then_some(0),
+            Algoritmo::RoundRobin => {
+                // Las llegadas ya se encolaron, así que el desalojado va detrás de ellas
+                if let Some(a) = self.actual.filter(|_| self.usado >= self.quantum - EPSILON) {
+                    self.cola.push_back(a);
+                    self.actual = None;
+                }
+                (self.actual.is_none() && !self.cola.is_empty()).then_some(0)
+            }
+            // La cola está en orden de admisión (por llegada), así que basta desempatar
+            // por prioridad
+            Algoritmo::FifoPrioridad => self
+                .posicion_min(|i, j| {
+                    let (p, q) = (&self.procesos[i], &self.procesos[j]);
+                    p.llegada
+                        .total_cmp(&q.llegada)
+                        .then(p.prioridad.cmp(&q.prioridad))
+                })
+                .filter(|_| self.actual.is_none()),
+            Algoritmo::Sjf => self
+                .posicion_min(|i, j| cpu(i).total_cmp(&cpu(j)))
+                .filter(|_| self.actual.is_none()),
+            Algoritmo::Ljf => self
+                .posicion_min(|i, j| cpu(j).total_cmp(&cpu(i)))
+                .filter(|_| self.actual.is_none()),
+            Algoritmo::Prioridad => self
+                .posicion_min(|i, j| prioridad(i).cmp(&prioridad(j)))
+                .filter(|_| self.actual.is_none()),
+            // Solo se desaloja al proceso en curso si hay otro con clave estrictamente menor
+            Algoritmo::Srtf => self
+                .posicion_min(|i, j| self.restante[i].total_cmp(&self.restante[j]))
+                .filter(|&k| {
+                    self.actual
+                        .is_none_or(|a| self.restante[self.cola[k]] < self.restante[a] - EPSILON)
+                }),
+            Algoritmo::PrioridadExpropiativa => self
+                .posicion_min(|i, j| prioridad(i).cmp(&prioridad(j)))
+                .filter(|&k| {
+                    self.actual
+                        .is_none_or(|a| prioridad(self.cola[k]) < prioridad(a))
+                }),
+        };
+
+        if let Some(k) = elegido {
+            let desalojado = self.actual.take();
+            self.actual = self.cola.remove(k);
+            self.usado = 0.0;
+            if let Some(a) = desalojado {
+                self.cola.push_back(a);
+            }
+        }
+    }
+
+    pub fn avanzar_tick(&mut self) -> EstadoSimulacion {
+        if self.terminada() {
+            return self.estado();
+        }
+        self.admitir();
+        self.despachar();
+
+        self.ultimo_tick = self.actual;
+        // El tick se corta en la próxima llegada y, en Round Robin, al agotarse el
+        // quantum, para que los instantes coincidan con los de planificador.rs
+        let mut paso: f64 = 1.0;
+        if let Some(&i) = self.orden.get(self.siguiente) {
+            paso = paso.min(self.procesos[i].llegada - self.reloj);
+        }
+        if let Some(i) = self.actual {
+            paso = paso.min(self.restante[i]);
+            if self.algoritmo == Algoritmo::RoundRobin {
+                paso = paso.min(self.quantum - self.usado);
+            }
+            self.restante[i] -= paso;
+            self.usado += paso;
+            if self.restante[i] <= EPSILON {
+                self.actual = None;
+            }
+        }
+        self.reloj += paso;
+        self.admitir();
+        self.estado()
+    }
+
+    pub fn estado(&self) -> EstadoSimulacion {
+        EstadoSimulacion {
+            reloj: self.reloj,
+            ejecutando: self.ultimo_tick.map(|i| self.procesos[i].pid.clone()),
+            cola: self
+                .cola
+                .iter()
+                .map(|&i| self.procesos[i].pid.clone())
+                .collect(),
+            restantes: self
+                .procesos
+                .iter()
+                .zip(&self.restante)
+                .map(|(p, &restante)| RestanteProceso {
+                    pid: p.pid.clone(),
+                    restante,
+                })
+                .collect(),
+            terminada: self.terminada(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planificador::{round_robin, PID_OCIOSO};
+
+    fn entrada(pid: &str, llegada: f64, rafaga: f64) -> ProcesoEntrada {
+        ProcesoEntrada {
+            pid: pid.to_string(),
+            llegada,
+            rafaga,
+            prioridad: 0,
+            rafagas: Vec::new(),
+        }
+    }
+
+    // Avanza hasta el final y junta los ticks contiguos del mismo proceso
+    fn gantt_por_ticks(mut simulador: Simulador) -> Vec<(String, f64, f64)> {
+        let mut gantt: Vec<(String, f64, f64)> = Vec::new();
+        let mut reloj = simulador.estado().reloj;
+        loop {
+            let estado = simulador.avanzar_tick();
+            let pid = estado.ejecutando.unwrap_or_else(|| PID_OCIOSO.to_string());
+            match gantt.last_mut() {
+                Some(ultimo) if ultimo.0 == pid && ultimo.2 == reloj => ultimo.2 = estado.reloj,
+                _ => gantt.push((pid, reloj, estado.reloj)),
+            }
+            reloj = estado.reloj;
+            if estado.terminada {
+                return gantt;
+            }
+        }
+    }
+
+    #[test]
+    fn round_robin_por_ticks_coincide_con_el_planificador() {
+        // Quantum fraccionario, una llegada a mitad de tick y un hueco ocioso
+        let procesos = vec![
+            entrada("A", 0.0, 3.5),
+            entrada("B", 0.5, 2.0),
+            entrada("C", 7.25, 1.0),
+        ];
+        let esperado: Vec<(String, f64, f64)> = round_robin(&procesos, 2.5, 0.0)
+            .gantt
+            .into_iter()
+            .map(|s| (s.pid, s.inicio, s.fin))
+            .collect();
+
+        let simulador = Simulador::nuevo(procesos, Algoritmo::RoundRobin, 2.5);
+        assert_eq!(gantt_por_ticks(simulador), esperado);
+    }
+}